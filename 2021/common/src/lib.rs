@@ -0,0 +1,35 @@
+//! Helpers shared by the 2021 days, depended on as
+//! `common = { package = "common2021", path = "../common" }`.
+
+use anyhow::{ensure, Context};
+
+/// A rectangular grid of single digits, stored row by row.
+#[derive(Debug)]
+pub struct Digits {
+    /// The digits, `cols` of them for each row.
+    pub data: Vec<u32>,
+    /// Number of rows.
+    pub rows: usize,
+    /// Number of digits in each row.
+    pub cols: usize,
+}
+
+/// Parse a grid of digits with one row per line, as used by the heightmap,
+/// octopus and chiton puzzles.
+pub fn digits(s: &str) -> anyhow::Result<Digits> {
+    let cols = s.lines().next().map_or(0, str::len);
+    let mut data = vec![];
+    let mut rows = 0;
+
+    for line in s.lines() {
+        ensure!(line.len() == cols, "Invalid length for row {}", rows);
+        for c in line.chars() {
+            data.push(c.to_digit(10)
+                .with_context(|| format!("Invalid digit: {}", c))?
+            );
+        }
+        rows += 1;
+    }
+
+    Ok(Digits {data, rows, cols})
+}
@@ -0,0 +1,422 @@
+//! Day 1: Sonar Sweep.
+
+use std::fmt;
+use std::io::BufRead;
+use std::str::FromStr;
+
+use anyhow::{bail, Context};
+use aoc::num::Int;
+
+/// A depth measurement.
+pub type Num = aoc::num::I32;
+
+/// How the sum of a window has to change from the window before to be
+/// counted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// The sum grows.
+    Increase,
+    /// The sum shrinks.
+    Decrease,
+    /// The sum grows or stays the same.
+    NonDecrease,
+}
+
+impl Mode {
+    /// Whether going from `before` to `after` is a change of this kind.
+    pub fn counts(self, before: Num, after: Num) -> bool {
+        match self {
+            Self::Increase => after > before,
+            Self::Decrease => after < before,
+            Self::NonDecrease => after >= before,
+        }
+    }
+}
+
+impl FromStr for Mode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "increase" => Ok(Self::Increase),
+            "decrease" => Ok(Self::Decrease),
+            "non-decrease" => Ok(Self::NonDecrease),
+            _ => bail!("Unknown mode: {} (expected increase, decrease or non-decrease)", s),
+        }
+    }
+}
+
+impl fmt::Display for Mode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Increase => "increase",
+            Self::Decrease => "decrease",
+            Self::NonDecrease => "non-decrease",
+        })
+    }
+}
+
+/// Parse the depth measurements, one per line.
+pub fn parse(input: &str) -> anyhow::Result<Vec<Num>> {
+    Ok(input
+        .lines()
+        .map(str::parse::<Num>)
+        .collect::<Result<Vec<_>, _>>()?)
+}
+
+/// The start of every window of `k` measurements whose sum changes from
+/// the window before it as `mode` asks, panicking when `k` is 0.
+///
+/// Neighbouring windows share all but the measurement leaving and the one
+/// entering, so comparing those two is enough and no sum is ever taken.
+///
+/// ```
+/// # use day01_2021::Mode;
+/// let depths = day01_2021::parse("199\n200\n208\n210\n200\n207\n240\n269\n260\n263")?;
+///
+/// let increases: Vec<_> = day01_2021::changes(&depths, 3, Mode::Increase).collect();
+/// assert_eq!(increases, [1, 4, 5, 6, 7]);
+/// assert_eq!(day01_2021::changes(&depths, 3, Mode::Decrease).count(), 1);
+/// assert_eq!(day01_2021::changes(&depths, 3, Mode::NonDecrease).count(), 6);
+/// # anyhow::Ok(())
+/// ```
+pub fn changes(depths: &[Num], k: usize, mode: Mode) -> impl Iterator<Item = usize> + '_ {
+    assert!(k > 0, "A window holds at least one measurement");
+
+    depths
+        .windows(k + 1)
+        .enumerate()
+        .filter(move |(_, depth)| mode.counts(depth[0], depth[k]))
+        .map(|(i, _)| i + 1)
+}
+
+/// Counts window sum changes like [`changes`] over measurements fed one
+/// at a time, keeping only the last `k` of them.
+#[derive(Debug, Clone)]
+pub struct Counter {
+    mode: Mode,
+    ring: Vec<Num>,
+    seen: usize,
+    count: usize,
+}
+
+impl Counter {
+    /// A counter for windows of `k` measurements, panicking when `k` is 0.
+    pub fn new(k: usize, mode: Mode) -> Self {
+        assert!(k > 0, "A window holds at least one measurement");
+
+        Self {mode, ring: vec![Num::default(); k], seen: 0, count: 0}
+    }
+
+    /// Add the next measurement, returning whether its window counted.
+    pub fn push(&mut self, depth: Num) -> bool {
+        // The slot about to be overwritten holds the measurement leaving
+        let slot = self.seen % self.ring.len();
+        let counted = self.seen >= self.ring.len() && self.mode.counts(self.ring[slot], depth);
+
+        self.ring[slot] = depth;
+        self.seen += 1;
+        self.count += counted as usize;
+
+        counted
+    }
+
+    /// How many windows counted so far.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// How many measurements were pushed so far.
+    pub fn seen(&self) -> usize {
+        self.seen
+    }
+}
+
+/// Feed every measurement in `reader` to all `counters`, one line at a time
+/// so memory use does not grow with the input, and call `progress` after
+/// every `every` measurements when given.
+pub fn stream(
+    mut reader: impl BufRead,
+    counters: &mut [Counter],
+    every: Option<usize>,
+    mut progress: impl FnMut(&[Counter]),
+) -> anyhow::Result<()> {
+    let mut line = String::new();
+    let mut number = 0;
+
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+        number += 1;
+
+        let text = line.trim_start_matches('\u{feff}').trim();
+        if text.is_empty() {
+            continue;
+        }
+        let depth: Num = text.parse()
+            .with_context(|| format!("Line {}: invalid depth: {}", number, text))?;
+
+        for counter in counters.iter_mut() {
+            counter.push(depth);
+        }
+        if let (Some(every), Some(counter)) = (every, counters.first()) {
+            if counter.seen() % every == 0 {
+                progress(counters);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Measurements `start` to `end`, both included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Run {
+    /// Index of the first measurement.
+    pub start: usize,
+    /// Index of the last measurement.
+    pub end: usize,
+}
+
+impl Run {
+    /// How many measurements are in the run.
+    pub fn length(&self) -> usize {
+        self.end - self.start + 1
+    }
+}
+
+/// The change from measurement `index - 1` to measurement `index`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Jump {
+    /// Index of the measurement after the change.
+    pub index: usize,
+    /// Measurement `index - 1`.
+    pub from: Num,
+    /// Measurement `index`.
+    pub to: Num,
+}
+
+impl Jump {
+    /// How much deeper measurement `index` is, negative when shallower.
+    pub fn change(&self) -> Num {
+        self.to - self.from
+    }
+}
+
+/// An overview of the shape of a sonar sweep.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile {
+    /// How many measurements the sweep has.
+    pub measurements: usize,
+    /// The first of the longest runs where every measurement is deeper
+    /// than the one before, if there is one.
+    pub longest_increase: Option<Run>,
+    /// Likewise for every measurement shallower than the one before.
+    pub longest_decrease: Option<Run>,
+    /// Measurements shallower than both neighbours; the first and last
+    /// have only one and are never included.
+    pub local_minima: Vec<usize>,
+    /// Measurements deeper than both neighbours.
+    pub local_maxima: Vec<usize>,
+    /// The first of the largest changes between neighbours either way.
+    pub largest_jump: Option<Jump>,
+}
+
+// The first longest run of neighbours that all compare as `mode` asks
+fn longest_run(depths: &[Num], mode: Mode) -> Option<Run> {
+    let mut longest: Option<Run> = None;
+    let mut start = 0;
+
+    for i in 1 .. depths.len() {
+        if !mode.counts(depths[i - 1], depths[i]) {
+            start = i;
+            continue;
+        }
+        let run = Run {start, end: i};
+        if longest.is_none_or(|l| run.length() > l.length()) {
+            longest = Some(run);
+        }
+    }
+
+    longest
+}
+
+/// Profile the measurements.
+///
+/// ```
+/// # use day01_2021::Run;
+/// let depths = day01_2021::parse("199\n200\n208\n210\n200\n207\n240\n269\n260\n263")?;
+/// let profile = day01_2021::profile(&depths);
+///
+/// assert_eq!(profile.longest_increase, Some(Run {start: 0, end: 3}));
+/// assert_eq!(profile.longest_decrease, Some(Run {start: 3, end: 4}));
+/// assert_eq!(profile.local_minima, [4, 8]);
+/// assert_eq!(profile.local_maxima, [3, 7]);
+/// assert_eq!(profile.largest_jump.map(|j| j.index), Some(6));
+/// # anyhow::Ok(())
+/// ```
+pub fn profile(depths: &[Num]) -> Profile {
+    let mut local_minima = vec![];
+    let mut local_maxima = vec![];
+    for (i, depth) in depths.windows(3).enumerate() {
+        if depth[1] < depth[0] && depth[1] < depth[2] {
+            local_minima.push(i + 1);
+        }
+        if depth[1] > depth[0] && depth[1] > depth[2] {
+            local_maxima.push(i + 1);
+        }
+    }
+
+    let mut largest_jump: Option<Jump> = None;
+    for (i, depth) in depths.windows(2).enumerate() {
+        let jump = Jump {index: i + 1, from: depth[0], to: depth[1]};
+        if largest_jump.is_none_or(|j| jump.change().abs() > j.change().abs()) {
+            largest_jump = Some(jump);
+        }
+    }
+
+    Profile {
+        measurements: depths.len(),
+        longest_increase: longest_run(depths, Mode::Increase),
+        longest_decrease: longest_run(depths, Mode::Decrease),
+        local_minima,
+        local_maxima,
+        largest_jump,
+    }
+}
+
+fn join(indices: &[usize], separator: &str) -> String {
+    indices.iter().map(|i| i.to_string()).collect::<Vec<_>>().join(separator)
+}
+
+impl Profile {
+    /// The profile as a single line of JSON, with `null` for the runs and
+    /// the jump when there are none.
+    pub fn to_json(&self) -> String {
+        let run = |run: Option<Run>| match run {
+            Some(r) => format!(
+                "{{\"start\":{},\"end\":{},\"length\":{}}}",
+                r.start, r.end, r.length(),
+            ),
+            None => String::from("null"),
+        };
+        let jump = match self.largest_jump {
+            Some(j) => format!(
+                "{{\"index\":{},\"from\":{},\"to\":{},\"change\":{}}}",
+                j.index, j.from, j.to, j.change(),
+            ),
+            None => String::from("null"),
+        };
+
+        format!(
+            "{{\"measurements\":{},\"longest_increase\":{},\"longest_decrease\":{},\
+            \"local_minima\":[{}],\"local_maxima\":[{}],\"largest_jump\":{}}}",
+            self.measurements,
+            run(self.longest_increase),
+            run(self.longest_decrease),
+            join(&self.local_minima, ","),
+            join(&self.local_maxima, ","),
+            jump,
+        )
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let run = |run: Option<Run>| match run {
+            Some(r) => format!("{} measurements, {} to {}", r.length(), r.start, r.end),
+            None => String::from("none"),
+        };
+        let indices = |indices: &[usize]| match indices {
+            [] => String::from("none"),
+            _ => join(indices, ", "),
+        };
+
+        writeln!(f, "measurements: {}", self.measurements)?;
+        writeln!(f, "longest increase: {}", run(self.longest_increase))?;
+        writeln!(f, "longest decrease: {}", run(self.longest_decrease))?;
+        writeln!(f, "local minima: {}", indices(&self.local_minima))?;
+        writeln!(f, "local maxima: {}", indices(&self.local_maxima))?;
+        match self.largest_jump {
+            Some(j) => write!(
+                f,
+                "largest jump: {:+} at {}, {} to {}",
+                j.change().get(),
+                j.index,
+                j.from,
+                j.to,
+            ),
+            None => write!(f, "largest jump: none"),
+        }
+    }
+}
+
+/// Every measurement replaced by the median of itself and the `width / 2`
+/// on either side, which drops single spikes without moving anything.
+/// Near the ends the window shrinks to keep the measurement in its middle,
+/// so the first and last measurements stay as they are.
+///
+/// Panics if `width` is even.
+pub fn median_smooth(depths: &[Num], width: usize) -> Vec<Num> {
+    assert!(width % 2 == 1, "A median window holds an odd number of measurements");
+
+    let mut window = Vec::with_capacity(width);
+    (0 .. depths.len())
+        .map(|i| {
+            let half = (width / 2).min(i).min(depths.len() - 1 - i);
+            window.clear();
+            window.extend_from_slice(&depths[i - half ..= i + half]);
+            window.sort_unstable();
+            window[half]
+        })
+        .collect()
+}
+
+/// The number of increases of more than `threshold` with hysteresis.
+///
+/// The last depth that got out of the dead-band is kept as a reference,
+/// and only a measurement more than `threshold` from it counts as a
+/// change, up or down, and becomes the new reference. Wobbles smaller
+/// than the band are never counted however often they repeat, while a slow
+/// drift is counted once it adds up. With a threshold of 0 this is part 1.
+///
+/// ```
+/// let depths = day01_2021::parse("100\n101\n100\n101\n100\n103\n104\n105\n106")?;
+/// let zero = "0".parse()?;
+/// let one = "1".parse()?;
+///
+/// assert_eq!(day01_2021::dead_band_increases(&depths, zero), day01_2021::part1(&depths));
+/// assert_eq!(day01_2021::dead_band_increases(&depths, one), 2);
+/// # anyhow::Ok(())
+/// ```
+pub fn dead_band_increases(depths: &[Num], threshold: Num) -> usize {
+    let Some((&first, rest)) = depths.split_first() else {
+        return 0;
+    };
+    let mut reference = first;
+    let mut count = 0;
+
+    for &depth in rest {
+        if depth - reference > threshold {
+            count += 1;
+            reference = depth;
+        } else if reference - depth > threshold {
+            reference = depth;
+        }
+    }
+
+    count
+}
+
+/// Part 1: the number of measurements deeper than the one before.
+pub fn part1(depths: &[Num]) -> usize {
+    changes(depths, 1, Mode::Increase).count()
+}
+
+/// Part 2: the number of three measurement sliding windows with a larger
+/// sum than the window before.
+pub fn part2(depths: &[Num]) -> usize {
+    changes(depths, 3, Mode::Increase).count()
+}
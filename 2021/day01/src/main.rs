@@ -1,26 +1,163 @@
-pub fn main() -> anyhow::Result<()> {
-    let input = include_str!("../input.txt")
-        .lines()
-        .map(str::parse::<i32>)
-        .collect::<Result<Vec<_>, _>>()?;
+//! Runs day 1, Sonar Sweep, on an input and prints the answers.
+
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::str::FromStr;
+
+use anyhow::{bail, ensure, Context};
+use clap::Parser;
+
+/// How to print the depth profile.
+#[derive(Debug, Clone, Copy)]
+enum Format {
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => bail!("Unknown format: {} (expected text or json)", s),
+        }
+    }
+}
+
+#[derive(Debug, Parser)]
+struct Cli {
+    #[command(flatten)]
+    args: aoc::Args,
+
+    /// Count changes between sums of this many measurements instead of
+    /// solving both parts
+    #[arg(long)]
+    window: Option<usize>,
+
+    /// Change to count: increase, decrease or non-decrease
+    #[arg(long, default_value_t = day01_2021::Mode::Increase, requires = "window")]
+    mode: day01_2021::Mode,
+
+    /// Also print where every counted window starts, comma separated
+    #[arg(long, requires = "window", conflicts_with = "stream")]
+    indices: bool,
+
+    /// Read the input a line at a time in constant memory, from standard
+    /// input when the input is - or not given
+    #[arg(long)]
+    stream: bool,
+
+    /// Print the running counts to stderr every this many measurements
+    /// while streaming
+    #[arg(long, requires = "stream")]
+    every: Option<usize>,
+
+    /// Print a profile of the sweep instead of solving both parts: the
+    /// longest runs, local extremes and largest jump, as text or json
+    #[arg(long, value_name = "FORMAT", conflicts_with_all = ["window", "stream"])]
+    profile: Option<Format>,
+
+    /// Also count increases ignoring any within this far of the last depth
+    /// counted, and print both counts instead of solving both parts
+    #[arg(long, value_name = "DEPTH", conflicts_with_all = ["window", "stream", "profile"])]
+    dead_band: Option<day01_2021::Num>,
+
+    /// Median smooth the measurements over windows of this many before
+    /// counting with the dead-band
+    #[arg(long, value_name = "WIDTH", requires = "dead_band")]
+    median: Option<usize>,
+}
+
+fn stream(cli: &Cli) -> anyhow::Result<()> {
+    let mut counters = match cli.window {
+        Some(k) => vec![day01_2021::Counter::new(k, cli.mode)],
+        None => vec![
+            day01_2021::Counter::new(1, day01_2021::Mode::Increase),
+            day01_2021::Counter::new(3, day01_2021::Mode::Increase),
+        ],
+    };
+    let progress = |counters: &[day01_2021::Counter]| {
+        let counts: Vec<String> = counters.iter().map(|c| c.count().to_string()).collect();
+        eprintln!("after {} measurements: {}", counters[0].seen(), counts.join(", "));
+    };
+
+    match cli.args.input.as_deref() {
+        Some(path) if path != "-" => {
+            let file = File::open(path)
+                .with_context(|| format!("Could not open {}", path.display()))?;
+            day01_2021::stream(BufReader::new(file), &mut counters, cli.every, progress)?;
+        },
+        _ => day01_2021::stream(io::stdin().lock(), &mut counters, cli.every, progress)?,
+    }
+
+    for counter in &counters {
+        println!("{}", counter.count());
+    }
+
+    Ok(())
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    cli.args.setup();
+
+    if let Some(k) = cli.window {
+        ensure!(k > 0, "A window holds at least one measurement");
+    }
+    if let Some(threshold) = cli.dead_band {
+        ensure!(threshold >= day01_2021::Num::default(), "The dead-band can not be negative");
+    }
+    if let Some(width) = cli.median {
+        ensure!(width % 2 == 1, "A median window holds an odd number of measurements");
+    }
+    ensure!(cli.every != Some(0), "Running counts need at least one measurement between them");
+    if cli.stream {
+        return stream(&cli);
+    }
+
+    let input = cli.args.input(aoc::puzzle!())?;
+    let input = aoc::phase("parse", || day01_2021::parse(&input))?;
+
+    if let Some(format) = cli.profile {
+        let profile = day01_2021::profile(&input);
+        match format {
+            Format::Text => println!("{}", profile),
+            Format::Json => println!("{}", profile.to_json()),
+        }
+
+        return Ok(());
+    }
+
+    if let Some(threshold) = cli.dead_band {
+        let smoothed = cli.median.map(|width| day01_2021::median_smooth(&input, width));
+        let depths = smoothed.as_deref().unwrap_or(&input);
+        println!(
+            "plain: {}, filtered: {}",
+            day01_2021::part1(&input),
+            day01_2021::dead_band_increases(depths, threshold),
+        );
+
+        return Ok(());
+    }
+
+    if let Some(k) = cli.window {
+        let changes: Vec<usize> = day01_2021::changes(&input, k, cli.mode).collect();
+        println!("{}", changes.len());
+        if cli.indices {
+            let indices: Vec<String> = changes.iter().map(|i| i.to_string()).collect();
+            println!("{}", indices.join(","));
+        }
+
+        return Ok(());
+    }
 
     // Part 1
-    println!(
-        "{}",
-        input
-            .windows(2)
-            .filter(|depth| depth[1] > depth[0])
-            .count(),
-    );
+    println!("{}", aoc::phase("part1", || day01_2021::part1(&input)));
 
     // Part 2
-    println!(
-        "{}",
-        input
-            .windows(4)
-            .filter(|depth| depth[3] > depth[0])
-            .count(),
-    );
+    println!("{}", aoc::phase("part2", || day01_2021::part2(&input)));
 
     Ok(())
 }
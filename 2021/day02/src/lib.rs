@@ -0,0 +1,199 @@
+//! Day 2: Dive!
+
+use std::str::FromStr;
+
+use anyhow::{bail, Context};
+
+/// A distance or depth.
+pub type Num = aoc::num::I32;
+
+/// One line of the planned course.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    /// `forward X`.
+    Forward(Num),
+    /// `up X`.
+    Up(Num),
+    /// `down X`.
+    Down(Num),
+}
+
+impl FromStr for Command {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let mut words = s.split(' ');
+        let cmd = words.next()
+            .context("Missing command")?;
+        let num = words.next()
+            .context("Missing number")?
+            .parse::<Num>()?;
+
+        match cmd {
+            "forward" => Ok(Self::Forward(num)),
+            "up" => Ok(Self::Up(num)),
+            "down" => Ok(Self::Down(num)),
+            _ => bail!("Invalid command: {}", cmd)
+        }
+    }
+}
+
+/// Where the submarine is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pos {
+    /// Horizontal position.
+    pub x: Num,
+    /// Depth.
+    pub y: Num,
+    /// Where the submarine points, only used by the part 2 reading.
+    pub aim: Num,
+}
+
+impl Pos {
+    /// Horizontal position multiplied by depth, the puzzle answer.
+    pub fn product(&self) -> Num {
+        self.x * self.y
+    }
+
+    /// The starting position.
+    pub fn origin() -> Self {
+        Self{x: Num::default(), y: Num::default(), aim: Num::default()}
+    }
+}
+
+/// How the submarine reads the commands of the course.
+///
+/// A model keeps whatever state it needs, so it can be picked by name at
+/// run time with [`model`] and only has to say what its answer is.
+pub trait MovementModel {
+    /// The name the model is selected by.
+    fn name(&self) -> &'static str;
+
+    /// Move as the command says.
+    fn apply(&mut self, cmd: &Command);
+
+    /// The puzzle answer for where the submarine is now.
+    fn answer(&self) -> Num;
+}
+
+/// Part 1 reading, where up and down change depth.
+#[derive(Debug, Clone, Copy)]
+pub struct Plain(pub Pos);
+
+impl Default for Plain {
+    fn default() -> Self {
+        Self(Pos::origin())
+    }
+}
+
+impl MovementModel for Plain {
+    fn name(&self) -> &'static str {
+        "plain"
+    }
+
+    fn apply(&mut self, cmd: &Command) {
+        let pos = self.0;
+        self.0 = match *cmd {
+            Command::Forward(n) => Pos{x: pos.x + n, ..pos},
+            Command::Up(n) => Pos{y: pos.y - n, ..pos},
+            Command::Down(n) => Pos{y: pos.y + n, ..pos},
+        };
+    }
+
+    fn answer(&self) -> Num {
+        self.0.product()
+    }
+}
+
+/// Part 2 reading, where up and down change the aim and forward dives
+/// along it.
+#[derive(Debug, Clone, Copy)]
+pub struct Aimed(pub Pos);
+
+impl Default for Aimed {
+    fn default() -> Self {
+        Self(Pos::origin())
+    }
+}
+
+impl MovementModel for Aimed {
+    fn name(&self) -> &'static str {
+        "aim"
+    }
+
+    fn apply(&mut self, cmd: &Command) {
+        let pos = self.0;
+        self.0 = match *cmd {
+            Command::Forward(n) => Pos{
+                x: pos.x + n,
+                y: pos.y + n * pos.aim,
+                ..pos
+            },
+            Command::Up(n) => Pos{aim: pos.aim - n, ..pos},
+            Command::Down(n) => Pos{aim: pos.aim + n, ..pos},
+        };
+    }
+
+    fn answer(&self) -> Num {
+        self.0.product()
+    }
+}
+
+fn boxed<M: MovementModel + Default + 'static>() -> Box<dyn MovementModel> {
+    Box::new(M::default())
+}
+
+/// Every model at its start, the part 1 one first. A new model only has
+/// to be added here to be selectable.
+const MODELS: &[fn() -> Box<dyn MovementModel>] = &[boxed::<Plain>, boxed::<Aimed>];
+
+/// Names of every model [`model`] knows, the part 1 one first.
+pub fn names() -> Vec<&'static str> {
+    MODELS.iter().map(|new| new().name()).collect()
+}
+
+/// The model called `name`, at its start.
+///
+/// ```
+/// let course = day02_2021::parse("forward 5\ndown 5\nforward 8\nup 3\ndown 8\nforward 2")?;
+/// let mut aim = day02_2021::model("aim")?;
+///
+/// assert_eq!(day02_2021::follow(aim.as_mut(), &course), day02_2021::part2(&course));
+/// assert!(day02_2021::model("yaw").is_err());
+/// # anyhow::Ok(())
+/// ```
+pub fn model(name: &str) -> anyhow::Result<Box<dyn MovementModel>> {
+    MODELS.iter()
+        .map(|new| new())
+        .find(|model| model.name() == name)
+        .with_context(|| {
+            format!("Unknown model: {} (expected one of {})", name, names().join(", "))
+        })
+}
+
+/// Follow the whole course with `model`, returning its answer.
+pub fn follow(model: &mut dyn MovementModel, course: &[Command]) -> Num {
+    for cmd in course {
+        model.apply(cmd);
+    }
+
+    model.answer()
+}
+
+/// One command per line.
+pub fn parse(input: &str) -> anyhow::Result<Vec<Command>> {
+    input.lines()
+        .enumerate()
+        .map(|(i, line)| line.parse().with_context(|| format!("Line {}", i + 1)))
+        .collect()
+}
+
+/// Part 1: the product of the final position and depth.
+pub fn part1(course: &[Command]) -> Num {
+    follow(&mut Plain::default(), course)
+}
+
+/// Part 2: the product of the final position and depth using the aim.
+pub fn part2(course: &[Command]) -> Num {
+    follow(&mut Aimed::default(), course)
+}
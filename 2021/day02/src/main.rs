@@ -1,96 +1,39 @@
-use std::str::FromStr;
+//! Runs day 2, Dive!, on an input and prints the answers.
 
-use anyhow::{bail, Context};
+use clap::Parser;
 
-#[derive(Debug)]
-enum Command {
-    Forward(i32),
-    Up(i32),
-    Down(i32),
-}
-
-impl FromStr for Command {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> anyhow::Result<Self> {
-        let mut words = s.split(' ');
-        let cmd = words.next()
-            .context("Missing command")?;
-        let num = words.next()
-            .context("Missing number")?
-            .parse::<i32>()?;
-
-        match cmd {
-            "forward" => Ok(Self::Forward(num)),
-            "up" => Ok(Self::Up(num)),
-            "down" => Ok(Self::Down(num)),
-            _ => bail!("Invalid command: {}", cmd)
-        }
-    }
-}
-
-struct Pos {
-    x: i32,
-    y: i32,
-    aim: i32,
-}
-
-impl Pos {
-    fn product(&self) -> i32 {
-        self.x * self.y
-    }
+#[derive(Debug, Parser)]
+struct Cli {
+    #[command(flatten)]
+    args: aoc::Args,
 
-    fn origin() -> Self {
-        Self{x: 0, y: 0, aim: 0}
-    }
+    /// Follow the course with only the movement model of this name and
+    /// print its answer instead of solving both parts
+    #[arg(long)]
+    model: Option<String>,
 }
 
-// Part 1
-fn track_pos(pos: Pos, cmd: &str) -> anyhow::Result<Pos> {
-    let cmd = str::parse(cmd)?;
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    cli.args.setup();
 
-    Ok(match cmd {
-        Command::Forward(n) => Pos{x: pos.x + n, ..pos},
-        Command::Up(n) => Pos{y: pos.y - n, ..pos},
-        Command::Down(n) => Pos{y: pos.y + n, ..pos},
-    })
-}
+    // Before reading the input, so a typo fails fast
+    let model = cli.model.as_deref().map(day02_2021::model).transpose()?;
 
-// Part 2
-fn track_pos_with_aim(pos: Pos, cmd: &str) -> anyhow::Result<Pos> {
-    let cmd = str::parse(cmd)?;
+    let input = cli.args.input(aoc::puzzle!())?;
+    let input = aoc::phase("parse", || day02_2021::parse(&input))?;
 
-    Ok(match cmd {
-        Command::Forward(n) => Pos{
-            x: pos.x + n,
-            y: pos.y + n * pos.aim,
-            ..pos
-        },
-        Command::Up(n) => Pos{aim: pos.aim - n, ..pos},
-        Command::Down(n) => Pos{aim: pos.aim + n, ..pos},
-    })
-}
+    if let Some(mut model) = model {
+        println!("{}", day02_2021::follow(model.as_mut(), &input));
 
-pub fn main() -> anyhow::Result<()> {
-    let input = include_str!("../input.txt");
+        return Ok(());
+    }
 
     // Part 1
-    println!(
-        "{}",
-        input
-            .lines()
-            .try_fold(Pos::origin(), track_pos)?
-            .product(),
-    );
+    println!("{}", aoc::phase("part1", || day02_2021::part1(&input)));
 
     // Part 2
-    println!(
-        "{}",
-        input
-            .lines()
-            .try_fold(Pos::origin(), track_pos_with_aim)?
-            .product(),
-    );
+    println!("{}", aoc::phase("part2", || day02_2021::part2(&input)));
 
     Ok(())
 }
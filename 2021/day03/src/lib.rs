@@ -0,0 +1,142 @@
+//! Day 3: Binary Diagnostic.
+
+use anyhow::{bail, Context};
+use aoc::num::Int;
+
+/// A diagnostic number or rating.
+pub type Num = aoc::num::I32;
+
+/// Which bit is more common in one position of the report, and by how
+/// many.
+#[derive(Debug, Clone)]
+pub enum BinaryMajority {
+    /// Ones lead by this many.
+    One(u32),
+    /// Zeros lead by this many.
+    Zero(u32),
+}
+
+impl From<&BinaryMajority> for Num {
+    fn from(item: &BinaryMajority) -> Self {
+        match item {
+            BinaryMajority::One(_) => Num::new(1),
+            BinaryMajority::Zero(_) => Num::new(0),
+        }
+    }
+}
+
+
+impl BinaryMajority {
+    /// Count one more bit.
+    pub fn count_binary(&mut self, num: char) -> anyhow::Result<()> {
+        *self = match num {
+            '1' => self.count_one(),
+            '0' => self.count_zero(),
+            _ => bail!("Unknown character: {}", num)
+        };
+
+        Ok(())
+    }
+
+    fn count_zero(&self) -> Self {
+        match self {
+            Self::One(0) => Self::Zero(1),
+            Self::Zero(n) => Self::Zero(n + 1),
+            Self::One(n) => Self::One(n - 1),
+        }
+    }
+
+    fn count_one(&self) -> Self {
+        match self {
+            Self::Zero(0) => Self::One(1),
+            Self::Zero(n) => Self::Zero(n - 1),
+            Self::One(n) => Self::One(n + 1),
+        }
+    }
+}
+
+/// The majority bit of every position, most significant first.
+#[derive(Debug)]
+pub struct Digits(Vec<BinaryMajority>);
+
+impl From<Digits> for Num {
+    fn from(item: Digits) -> Self {
+        item.0.iter()
+            .fold(Num::default(), |acc, num| acc * 2 + Self::from(num))
+    }
+}
+
+impl Digits {
+    /// No bits counted yet for numbers of `size` bits.
+    pub fn with(size: usize) -> Self {
+        Digits(vec![BinaryMajority::Zero(0); size])
+    }
+
+    /// The gamma rate multiplied by the epsilon rate, its inverse.
+    pub fn product(self) -> Num {
+        let len = self.0.len();
+        let num: Num = self.into();
+
+        num * (num ^ ((2 << (len - 1)) - 1))
+    }
+}
+
+/// Count the bits of one line of the report.
+pub fn count_ones(mut ones: Digits, binary: &str) -> anyhow::Result<Digits> {
+    binary.char_indices().try_for_each(|(i, n)| {
+        ones.0[i].count_binary(n)
+    })?;
+
+    Ok(ones)
+}
+
+/// Filter the report down to one number by keeping, position by
+/// position, the numbers with the most common bit (or the least common
+/// one when `common` is false).
+pub fn bit_criteria(input: &str, common: bool) -> anyhow::Result<Num> {
+    let digits = input.find('\n').unwrap_or(input.len());
+    let mut lines: Vec<&str> = input.lines().collect();
+
+    for i in 0 .. digits {
+        let count = lines.len();
+        let mut ones = 0;
+
+        if count == 1 {
+            break;
+        }
+
+        for line in &lines {
+            if line.chars().nth(i)
+                .with_context(||
+                    format!("Could not get char {} from \"{}\"", i, line)
+                )? == '1' {
+                ones += 1;
+            }
+        }
+
+        let crit = if (ones * 2 >= count) == common { '1' } else { '0' };
+        lines.retain(
+            // Unwrap will not panic because we checked in the previous
+            // for loop and would return an error if that failed
+            |l| l.chars().nth(i).unwrap() == crit
+            );
+    }
+
+    Ok(Num::of(i32::from_str_radix(lines[0], 2)?))
+}
+
+/// Part 1: the power consumption, gamma rate times epsilon rate.
+pub fn part1(input: &str) -> anyhow::Result<Num> {
+    let digits = input.find('\n').unwrap_or(input.len());
+
+    Ok(input
+        .lines()
+        .try_fold(Digits::with(digits), count_ones)?
+        .product())
+}
+
+/// Part 2: the life support rating, oxygen generator rating times CO2
+/// scrubber rating.
+pub fn part2(input: &str) -> anyhow::Result<Num> {
+    Ok(bit_criteria(input, true)? * bit_criteria(input, false)?)
+}
@@ -0,0 +1,221 @@
+//! Day 4: Giant Squid.
+
+use std::str::FromStr;
+use std::collections::HashSet;
+
+use anyhow::{bail, ensure, Context};
+use tracing::{debug, trace};
+
+/// A bingo number or a score.
+pub type Num = aoc::num::I32;
+
+/// Rows of a bingo board.
+pub const ROWS: usize = 5;
+/// Columns of a bingo board.
+pub const COLS: usize = 5;
+
+/// The numbers of a bingo board not called yet, by row and by column.
+#[derive(Debug, Clone)]
+pub struct Board {
+    rows: [HashSet<Num>; ROWS],
+    cols: [HashSet<Num>; COLS],
+}
+
+impl FromStr for Board {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let mut rows: [HashSet<Num>; ROWS] = Default::default();
+        let mut cols: [HashSet<Num>; COLS] = Default::default();
+
+        for (i, line) in s.lines().enumerate() {
+            for (j, num) in line.split_whitespace()
+                .map(str::parse::<Num>).enumerate() {
+                let num = num?;
+
+                rows.get_mut(i)
+                    .with_context(||
+                        format!("Board has more than {} rows", ROWS)
+                    )?.insert(num);
+                cols.get_mut(j)
+                    .with_context(||
+                        format!("Board has more than {} columns", COLS)
+                    )?.insert(num);
+            }
+        }
+
+        Ok(Board {rows, cols})
+    }
+}
+
+impl Board {
+    /// Sum of the numbers not called yet.
+    pub fn sum(&self) -> Num {
+        self.rows.iter().flatten().sum()
+    }
+
+    /// Mark `num` and return whether that completed a row or column.
+    pub fn call(&mut self, num: Num) -> bool {
+        self.rows.iter_mut()
+            .for_each(|set| {set.remove(&num);});
+        self.cols.iter_mut()
+            .for_each(|set| {set.remove(&num);});
+
+        self.rows.iter()
+            .any(|set| set.is_empty())
+        || self.cols.iter()
+            .any(|set| set.is_empty())
+    }
+}
+
+/// Part 1: the score of the first board to win, its unmarked sum times
+/// the last number called.
+pub fn part1(nums: &[Num], mut boards: Vec<Board>) -> anyhow::Result<Num> {
+    for (call, &num) in nums.iter().enumerate() {
+        trace!(call, %num, "number called");
+        for (i, board) in boards.iter_mut().enumerate() {
+            if board.call(num) {
+                debug!(call, %num, board = i, sum = %board.sum(), "board won");
+                return Ok(board.sum() * num);
+            }
+        }
+    }
+
+    bail!("Failed to find a winning board")
+}
+
+/// Part 2: the score of the last board to win.
+pub fn part2(nums: &[Num], mut boards: Vec<Board>) -> anyhow::Result<Num> {
+    for (call, &num) in nums.iter().enumerate() {
+        let board_count = boards.len();
+        let mut new_boards = Vec::with_capacity(board_count);
+        trace!(call, %num, boards = board_count, "number called");
+
+        for mut board in boards.into_iter() {
+            if board.call(num) {
+                debug!(call, %num, sum = %board.sum(), "board won");
+                if board_count == 1 {
+                    return Ok(board.sum() * num);
+                }
+            } else {
+                new_boards.push(board);
+            }
+        }
+        boards = new_boards;
+    }
+
+    bail!("Failed to find a winning board")
+}
+
+/// Parse the numbers to call and the boards.
+pub fn parse(input: &str) -> anyhow::Result<(Vec<Num>, Vec<Board>)> {
+    let mut input = input.split("\n\n");
+    let nums = input.next()
+        .context("Empty input file")?
+        .split(',')
+        .map(str::parse::<Num>)
+        .collect::<Result<Vec<_>, _>>()?;
+    let boards = input
+        .map(str::parse::<Board>)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    anyhow::Ok((nums, boards))
+}
+
+/// A game in progress, stepped through in the REPL one call at a time.
+pub struct Bingo {
+    nums: Vec<Num>,
+    boards: Vec<Board>,
+    // The call each board won on
+    won: Vec<Option<usize>>,
+    called: usize,
+}
+
+impl Bingo {
+    fn board(&self, i: &str) -> anyhow::Result<usize> {
+        let i = i.parse()?;
+        ensure!(i < self.boards.len(), "There are only {} boards", self.boards.len());
+
+        Ok(i)
+    }
+}
+
+impl aoc::repl::Repl for Bingo {
+    const STEP: &'static str = "calling the next number";
+    const QUERIES: &'static [(&'static str, &'static str)] = &[
+        ("called", "numbers called so far"),
+        ("board <i>", "unmarked numbers left on board i, by row"),
+        ("score <i>", "score of board i if it has won"),
+    ];
+
+    fn load(input: &str) -> anyhow::Result<Self> {
+        let (nums, boards) = parse(input)?;
+        let won = vec![None; boards.len()];
+
+        Ok(Self {nums, boards, won, called: 0})
+    }
+
+    fn step(&mut self) -> anyhow::Result<String> {
+        let &num = self.nums.get(self.called)
+            .context("Every number has been called")?;
+        let mut winners = vec![];
+
+        for (i, board) in self.boards.iter_mut().enumerate() {
+            if self.won[i].is_none() && board.call(num) {
+                self.won[i] = Some(self.called);
+                winners.push(i.to_string());
+            }
+        }
+        self.called += 1;
+
+        Ok(format!(
+            "Call {}: {}, {}",
+            self.called,
+            num,
+            if winners.is_empty() {
+                String::from("no board won")
+            } else {
+                format!("board {} won", winners.join(", "))
+            },
+        ))
+    }
+
+    fn show(&self) -> String {
+        self.boards.iter()
+            .zip(&self.won)
+            .enumerate()
+            .map(|(i, (board, won))| match won {
+                Some(call) => format!("Board {}: won on call {}", i, call + 1),
+                None => format!("Board {}: unmarked numbers sum to {}", i, board.sum()),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn query(&self, name: &str, args: &[&str]) -> anyhow::Result<String> {
+        match (name, args) {
+            ("called", []) => Ok(self.nums[.. self.called].iter()
+                .map(Num::to_string)
+                .collect::<Vec<_>>()
+                .join(",")),
+            ("board", [i]) => {
+                let rows = self.boards[self.board(i)?].rows.iter()
+                    .map(|row| {
+                        let mut row: Vec<_> = row.iter().collect();
+                        row.sort_unstable();
+                        format!("{:?}", row)
+                    })
+                    .collect::<Vec<_>>();
+                Ok(rows.join("\n"))
+            },
+            ("score", [i]) => {
+                let i = self.board(i)?;
+                match self.won[i] {
+                    Some(call) => Ok((self.boards[i].sum() * self.nums[call]).to_string()),
+                    None => bail!("Board {} has not won", i),
+                }
+            },
+            _ => bail!("Unknown query: {} {}", name, args.join(" ")),
+        }
+    }
+}
@@ -0,0 +1,148 @@
+//! Day 5: Hydrothermal Venture.
+
+use std::str::FromStr;
+use std::collections::HashMap;
+
+use anyhow::Context;
+
+/// A coordinate on the ocean floor.
+pub type Num = aoc::num::I32;
+
+/// A point on the ocean floor, or the step between two points of a line.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct Pair {
+    /// Horizontal coordinate.
+    pub x: Num,
+    /// Vertical coordinate.
+    pub y: Num,
+}
+
+/// The points a line covers, from start to end.
+pub struct PointIter<'a> {
+    line: &'a Line,
+    n: Num,
+	done: bool,
+}
+
+impl Iterator for PointIter<'_> {
+    type Item = Pair;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let x = self.line.start.x + self.line.slope.x * self.n;
+        let y = self.line.start.y + self.line.slope.y * self.n;
+
+        let out = Pair{x, y};
+
+        if out == self.line.end {
+            self.done = true;
+        }
+        self.n += 1;
+
+        Some(out)
+    }
+}
+
+fn gcd(mut m: Num, mut n: Num) -> Num {
+	// Use Euclid's algorithm
+	while m != 0 {
+		let temp = m;
+		m = n % temp;
+		n = temp;
+	}
+	n.abs()
+}
+
+impl Pair {
+    fn slope(&self, end: &Self) -> Self {
+        let x = end.x - self.x;
+        let y = end.y - self.y;
+
+        let gcd = gcd(x, y);
+
+        Pair{x: x / gcd, y: y / gcd}
+    }
+}
+
+/// A line of hydrothermal vents.
+#[derive(Debug)]
+pub struct Line {
+    start: Pair,
+    end: Pair,
+    slope: Pair,
+}
+
+impl FromStr for Line {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let (s1, s2) = s.split_once(" -> ").context("Invalid line")?;
+
+        let parse_pair = |s: &str| -> anyhow::Result<Pair> {
+            let (x,y) = s.split_once(',').context("Invalid point")?;
+            Ok(Pair{x: x.parse()?, y: y.parse()?})
+        };
+
+        let start = parse_pair(s1)?;
+        let end = parse_pair(s2)?;
+
+        let slope = start.slope(&end);
+
+        Ok(Line{start, end, slope})
+    }
+}
+
+impl Line {
+    /// Whether the line is horizontal or vertical.
+    pub fn is_straight(&self) -> bool {
+        self.slope.x == 0 || self.slope.y == 0
+    }
+
+    /// Every point the line covers.
+    pub fn points(&self) -> PointIter<'_> {
+        PointIter{line: self, n: Num::default(), done: false}
+    }
+}
+
+/// Add the lines matching `predicate` to `map`, which counts the lines
+/// over each point, and return how many points have at least two.
+pub fn count_overlaps<P>(
+    lines: &[Line],
+    map: &mut HashMap<Pair, usize>,
+    predicate: P
+) -> usize
+where
+    P: FnMut(&&Line) -> bool,
+{
+    for line in lines.iter().filter(predicate) {
+        for point in line.points() {
+            *map.entry(point).or_insert(0) += 1;
+        }
+    }
+
+    map.iter()
+        .filter(|(_, v)| **v >= 2)
+        .count()
+}
+
+/// Parse the lines of vents, one per line.
+pub fn parse(input: &str) -> anyhow::Result<Vec<Line>> {
+    input.lines()
+        .map(str::parse::<Line>)
+        .collect()
+}
+
+/// Part 1: the points where horizontal and vertical lines overlap,
+/// counting the lines in `map`.
+pub fn part1(lines: &[Line], map: &mut HashMap<Pair, usize>) -> usize {
+    count_overlaps(lines, map, |l| l.is_straight())
+}
+
+/// Part 2: the points where any lines overlap, adding the diagonal lines
+/// to the `map` filled in by [`part1`].
+pub fn part2(lines: &[Line], map: &mut HashMap<Pair, usize>) -> usize {
+    count_overlaps(lines, map, |l| !l.is_straight())
+}
@@ -0,0 +1,115 @@
+//! Day 6: Lanternfish.
+
+use std::str::FromStr;
+
+use anyhow::bail;
+
+/// Timer values a fish can have, a new fish starts at the highest.
+pub const MAX_TIME: usize = 9;
+/// Days between two fish a grown fish spawns.
+pub const NORM_TIME: usize = 7;
+
+/// A number of fish.
+pub type Num = aoc::num::U64;
+
+/// How many fish there are with each timer value.
+#[derive(Debug, Clone)]
+pub struct Lungfishes(pub [Num; MAX_TIME]);
+
+impl FromStr for Lungfishes {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        Ok(Lungfishes(
+            s.trim()
+                .split(',')
+                .map(str::parse::<usize>)
+                .try_fold([Num::default(); MAX_TIME], |mut fish, num| {
+                    fish[num?] += 1;
+                    anyhow::Ok(fish)
+                })?
+        ))
+    }
+}
+
+impl Lungfishes {
+    /// Let one day pass.
+    pub fn step_day(&mut self) {
+        let spawning = self.0[0];
+        self.0.rotate_left(1);
+        self.0[NORM_TIME - 1] += spawning;
+    }
+
+    /// Let `days` days pass and return how many fish there are then.
+    pub fn step_days(&mut self, days: i32) -> Num {
+        for _ in 0 .. days {
+            self.step_day();
+        }
+
+        self.sum()
+    }
+
+    /// How many fish there are.
+    pub fn sum(&self) -> Num {
+        self.0.iter().sum()
+    }
+}
+
+/// The fish with the number of days passed, stepped through in the REPL
+/// one day at a time.
+pub struct School {
+    fish: Lungfishes,
+    day: usize,
+}
+
+impl aoc::repl::Repl for School {
+    const STEP: &'static str = "one day";
+    const QUERIES: &'static [(&'static str, &'static str)] = &[
+        ("count", "number of lanternfish"),
+        ("day", "days passed so far"),
+        ("timer <t>", "number of fish whose timer is at t"),
+    ];
+
+    fn load(input: &str) -> anyhow::Result<Self> {
+        Ok(Self {fish: str::parse(input)?, day: 0})
+    }
+
+    fn step(&mut self) -> anyhow::Result<String> {
+        self.fish.step_day();
+        self.day += 1;
+
+        Ok(format!("Day {}: {} fish", self.day, self.fish.sum()))
+    }
+
+    fn show(&self) -> String {
+        let timers = self.fish.0.iter()
+            .enumerate()
+            .map(|(timer, count)| format!("{}: {}", timer, count))
+            .collect::<Vec<_>>();
+
+        format!("After {} days:\n{}", self.day, timers.join("\n"))
+    }
+
+    fn query(&self, name: &str, args: &[&str]) -> anyhow::Result<String> {
+        match (name, args) {
+            ("count", []) => Ok(self.fish.sum().to_string()),
+            ("day", []) => Ok(self.day.to_string()),
+            ("timer", [t]) => match self.fish.0.get(t.parse::<usize>()?) {
+                Some(count) => Ok(count.to_string()),
+                None => bail!("Timers go from 0 to {}", MAX_TIME - 1),
+            },
+            _ => bail!("Unknown query: {} {}", name, args.join(" ")),
+        }
+    }
+}
+
+/// Part 1: the number of fish after 80 days.
+pub fn part1(fish: &mut Lungfishes) -> Num {
+    fish.step_days(80)
+}
+
+/// Part 2: the number of fish after 256 days, continuing from the fish
+/// [`part1`] left.
+pub fn part2(fish: &mut Lungfishes) -> Num {
+    fish.step_days(256 - 80)
+}
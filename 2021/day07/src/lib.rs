@@ -0,0 +1,101 @@
+//! Day 7: The Treachery of Whales.
+
+use aoc::num::Int;
+
+/// A crab position or an amount of fuel.
+pub type Num = aoc::num::I32;
+
+/// One candidate for the best position, or the two neighbours it lies
+/// between.
+pub enum Possible<T> {
+    /// A single best position.
+    Single(T),
+    /// The positions either side of the best one.
+    Pair(T, T),
+}
+
+impl<T: Ord + Copy> Possible<T> {
+    /// The smallest cost `func` gives for the candidates.
+    pub fn min(&self, func: impl Fn(T) -> T) -> T {
+        match &self {
+            Possible::Single(x) => func(*x),
+            Possible::Pair(x, y) => func(*x).min(func(*y)),
+        }
+    }
+}
+
+/// The median of the positions, which minimizes the fuel at constant cost
+/// per step.
+pub fn median(vec: &mut [Num]) -> Possible<Num> {
+    let len = vec.len();
+    let mid = len / 2;
+    let (_, &mut out, _) = vec.select_nth_unstable(mid);
+
+    if len & 1 == 0 {
+        let (_, &mut out2, _) = vec.select_nth_unstable(mid + 1);
+
+        Possible::Pair(out, out2)
+    } else {
+        Possible::Single(out)
+    }
+}
+
+/// The mean of the positions, next to which lies the minimum of the fuel
+/// when each step costs one more than the last.
+pub fn mean(vec: &[Num]) -> Possible<Num> {
+    let sum: Num = vec.iter().sum();
+    let len = Num::of(vec.len());
+    let mean = sum / len;
+
+    if mean * len < sum {
+        Possible::Pair(mean, mean + 1)
+    } else {
+        Possible::Single(mean)
+    }
+}
+
+/// Fuel to move `num` steps when each step costs one more than the last.
+pub fn arith_sum(num: Num) -> Num {
+    num * (num + 1) / 2
+}
+
+/// Fuel for every crab to move to `pos`, with `cost` turning a distance
+/// into fuel.
+pub fn total_fuel(crabs: &[Num], pos: Num, cost: impl Fn(Num) -> Num) -> Num {
+    crabs.iter()
+        .map(|&x| cost((x - pos).abs()))
+        .sum()
+}
+
+/// The least fuel over every position between the outermost crabs,
+/// instead of relying on the median and mean.
+pub fn brute_force(crabs: &[Num], cost: impl Fn(Num) -> Num) -> Num {
+    let min = crabs.iter().copied().min().unwrap_or_default();
+    let max = crabs.iter().copied().max().unwrap_or_default();
+
+    (min.get() ..= max.get())
+        .map(|pos| total_fuel(crabs, Num::new(pos), &cost))
+        .min()
+        .unwrap_or_default()
+}
+
+/// Parse the comma separated crab positions.
+pub fn parse(input: &str) -> anyhow::Result<Vec<Num>> {
+    Ok(input
+        .trim()
+        .split(',')
+        .map(str::parse::<Num>)
+        .collect::<Result<Vec<_>, _>>()?)
+}
+
+/// Part 1: the least fuel to align the crabs when each step costs one.
+/// Reorders `crabs` to find their median.
+pub fn part1(crabs: &mut [Num]) -> Num {
+    median(crabs).min(|a| total_fuel(crabs, a, |d| d))
+}
+
+/// Part 2: the least fuel to align the crabs when each step costs one
+/// more than the last.
+pub fn part2(crabs: &[Num]) -> Num {
+    mean(crabs).min(|a| total_fuel(crabs, a, arith_sum))
+}
@@ -0,0 +1,110 @@
+//! Day 8: Seven Segment Search.
+
+use std::collections::HashSet;
+use std::iter::FromIterator;
+
+use anyhow::{bail, Context};
+use aoc::num::Int;
+
+/// An output value or their sum.
+pub type Num = aoc::num::I32;
+
+fn set2str(set: HashSet<char>) -> String {
+    set.iter().fold(String::new(), |mut s, c| {s.push(*c); s})
+}
+
+/// The number the decimal digits in `arr` make up.
+pub fn arr2num(arr: &[usize]) -> Num {
+    arr.iter().fold(Num::default(), |acc, &n| acc * 10 + Num::of(n))
+}
+
+/// Work out which segments light up for each digit from the ten unique
+/// signal patterns.
+pub fn get_signals(signals: &str) -> anyhow::Result<[HashSet<char>; 10]> {
+    let mut known: [HashSet<char>; 10] = Default::default();
+    let mut unknown = Vec::with_capacity(10);
+
+    // Prepare HashSets and find 1, 4, 7, and 8
+    for signal in signals.split(' ') {
+        match signal.len() {
+            2 => known[1].extend(signal.chars()),
+            3 => known[7].extend(signal.chars()),
+            4 => known[4].extend(signal.chars()),
+            7 => known[8].extend(signal.chars()),
+            _ => unknown.push(HashSet::from_iter(signal.chars()))
+        }
+    }
+
+    let segs_in_2: HashSet<_> = known[8].difference(&known[4])
+        .copied().collect();
+
+    for signal in unknown.into_iter() {
+        let sig_len = signal.len();
+
+        if sig_len == 5 {
+            if segs_in_2.is_subset(&signal) {
+                known[2] = signal;
+            } else if known[1].is_subset(&signal) {
+                known[3] = signal;
+            } else {
+                known[5] = signal;
+            }
+        } else if sig_len == 6 {
+            if known[4].is_subset(&signal) {
+                known[9] = signal;
+            } else if known[1].is_subset(&signal) {
+                known[0] = signal;
+            } else {
+                known[6] = signal;
+            }
+        } else {
+            bail!("Invalid signal: {}", set2str(signal));
+        }
+    }
+
+    Ok(known)
+}
+
+/// Decode the output digits of one entry.
+pub fn get_nums(line: &str) -> anyhow::Result<Vec<usize>> {
+    let (signals, encoded) = line.split_once(" | ")
+        .context("Invalid input: Missing '|'")?;
+    let known = get_signals(signals)?;
+
+    let mut nums = vec![];
+
+    let sets = encoded.split(' ')
+        .map(|s| HashSet::from_iter(s.chars()));
+    for set in sets {
+        nums.push(known.iter()
+            .position(|s| s == &set)
+            .with_context(||
+                format!("Unknown signal: {}", set2str(set))
+            )?);
+    }
+
+    Ok(nums)
+}
+
+/// Parse and decode the output digits of every entry.
+pub fn parse(input: &str) -> anyhow::Result<Vec<Vec<usize>>> {
+    input
+        .lines()
+        .map(get_nums)
+        .collect()
+}
+
+/// Part 1: how many output digits are a 1, 4, 7 or 8.
+pub fn part1(nums: &[Vec<usize>]) -> usize {
+    nums.iter()
+        .flatten()
+        .filter(|n| [1, 4, 7, 8].contains(n))
+        .count()
+}
+
+/// Part 2: the sum of the output values.
+pub fn part2(nums: &[Vec<usize>]) -> Num {
+    nums.iter()
+        .map(|arr| arr2num(&arr[..]))
+        .sum()
+}
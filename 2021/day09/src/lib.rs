@@ -0,0 +1,167 @@
+//! Day 9: Smoke Basin.
+
+use std::str::FromStr;
+
+use aoc::num::Int;
+
+/// A sum of risk levels or a product of basin sizes.
+pub type Num = aoc::num::U32;
+
+/// The heightmap of the cave floor.
+#[derive(Debug)]
+pub struct Matrix {
+    data: Vec<u32>,
+    rows: usize,
+    cols: usize,
+}
+
+impl FromStr for Matrix {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let common::Digits {data, rows, cols} = common::digits(s)?;
+
+        Ok(Matrix {data, rows, cols})
+    }
+}
+
+/// Every location of a heightmap with the heights around it, as
+/// `(row, column, height, adjacent heights)`.
+pub struct Adjacents<'a> {
+    matrix: &'a Matrix,
+    i: usize,
+    j: usize,
+}
+
+impl Matrix {
+    /// Iterate over every location with its adjacent heights.
+    pub fn adjacents(&self) -> Adjacents<'_> {
+        Adjacents {
+            matrix: self,
+            i: 0,
+            j: 0,
+        }
+    }
+
+    /// The height at row `i`, column `j`.
+    pub fn get(&self, i: usize, j: usize) -> Option<u32> {
+        if i < self.rows && j < self.cols {
+            Some(self.data[i * self.cols + j])
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a> Iterator for Adjacents<'a> {
+    type Item = (usize, usize, u32, Vec<u32>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mat = self.matrix;
+        if self.i == mat.rows {
+            return None;
+        }
+
+        // We make sure i and j are always in bounds
+        // so unwrapping will not panic
+        let num = mat.get(self.i, self.j).unwrap();
+        let i = self.i;
+        let j = self.j;
+        let mut adjs = vec![];
+
+        if self.i > 0 {
+            if let Some(n) = mat.get(self.i - 1, self.j) {
+                adjs.push(n);
+            }
+        }
+        if let Some(n) = mat.get(self.i + 1, self.j) {
+            adjs.push(n);
+        }
+        if self.j > 0 {
+            if let Some(n) = mat.get(self.i, self.j - 1) {
+                adjs.push(n);
+            }
+        }
+        if let Some(n) = mat.get(self.i, self.j + 1) {
+            adjs.push(n);
+
+            self.j += 1;
+        } else {
+            self.j = 0;
+            self.i += 1;
+        }
+
+        Some((i, j, num, adjs))
+    }
+}
+
+/// The low points, lower than every adjacent location, as
+/// `(row, column, height)`.
+pub fn get_low(mat: &Matrix) -> Vec<(usize, usize, u32)> {
+    let mut lows = vec![];
+
+    for (i, j, n, adj) in mat.adjacents() {
+        if adj.iter().all(|&x| x > n) {
+            lows.push((i, j, n));
+        }
+    }
+
+    lows
+}
+
+/// The size of the basin around each low point.
+pub fn get_basin_sizes(mat: &Matrix, lows: &[(usize, usize, u32)]) -> Vec<Num> {
+    let mut sizes = vec![];
+    let mut checked = vec![false; mat.rows * mat.cols];
+
+    for &(i, j, _) in lows {
+        sizes.push(basin_size(mat, i, j, &mut checked));
+    }
+
+    sizes
+}
+
+/// The size of the basin around row `i`, column `j`, skipping and marking
+/// the locations in `checked`.
+pub fn basin_size(mat: &Matrix, i: usize, j: usize, checked: &mut [bool]) -> Num {
+    let mut size = Num::default();
+    let mut to_check = vec![(i, j)];
+
+    while let Some((i, j)) = to_check.pop() {
+        if let Some(n) = mat.get(i, j) {
+            if checked[i * mat.cols + j] {
+                continue;
+            }
+            checked[i * mat.cols + j] = true;
+
+            if n < 9 {
+                size += 1;
+            } else {
+                continue;
+            }
+
+            if i > 0 {
+                to_check.push((i - 1, j));
+            }
+            if j > 0 {
+                to_check.push((i, j - 1));
+            }
+            to_check.push((i + 1, j));
+            to_check.push((i, j + 1));
+        }
+    }
+
+    size
+}
+
+/// Part 1: the sum of the risk levels of the low points from [`get_low`].
+pub fn part1(lows: &[(usize, usize, u32)]) -> Num {
+    lows.iter().map(|x| Num::of(x.2 + 1)).sum()
+}
+
+/// Part 2: the product of the sizes of the three largest basins.
+pub fn part2(mat: &Matrix, lows: &[(usize, usize, u32)]) -> Num {
+    let mut sizes = get_basin_sizes(mat, lows);
+    sizes.sort_unstable();
+    sizes.iter().rev().take(3).product()
+}
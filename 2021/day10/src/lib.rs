@@ -0,0 +1,158 @@
+//! Day 10: Syntax Scoring.
+
+use anyhow::bail;
+use aoc::num::Int;
+
+/// A syntax error or completion score.
+pub type Num = aoc::num::U64;
+
+/// An opening or closing bracket.
+#[derive(Debug)]
+pub enum Symbol {
+    /// Opens a chunk of this kind.
+    Open(SymbolType),
+    /// Closes the innermost open chunk, which has to be of this kind.
+    Close(SymbolType),
+}
+
+/// The kind of bracket.
+#[derive(Debug, PartialEq)]
+pub enum SymbolType {
+    /// `(` and `)`.
+    Parenthesis,
+    /// `[` and `]`.
+    SquareBracket,
+    /// `{` and `}`.
+    CurlyBrace,
+    /// `<` and `>`.
+    AngleBracket,
+}
+
+use self::{Symbol::*, SymbolType::*};
+
+impl Symbol {
+    /// The bracket for `c`.
+    pub fn from_char(c: char) -> anyhow::Result<Self> {
+        Ok(match c {
+            '(' => Open(Parenthesis),
+            '[' => Open(SquareBracket),
+            '{' => Open(CurlyBrace),
+            '<' => Open(AngleBracket),
+            ')' => Close(Parenthesis),
+            ']' => Close(SquareBracket),
+            '}' => Close(CurlyBrace),
+            '>' => Close(AngleBracket),
+            _   => bail!("Invalid character: {}", c)
+        })
+    }
+}
+
+impl SymbolType {
+    /// Syntax error score of an unexpected closing bracket of this kind.
+    pub fn score(&self) -> Num {
+        Num::new(match *self {
+            Parenthesis => 3,
+            SquareBracket => 57,
+            CurlyBrace => 1197,
+            AngleBracket => 25137,
+        })
+    }
+}
+
+/// The result of checking one line.
+#[derive(Debug)]
+pub enum Chunk {
+    /// A closing bracket of this kind did not match the open chunk.
+    Corrupt(SymbolType),
+    /// The line ended with these chunks still open, innermost last.
+    Incomplete(Vec<SymbolType>),
+    /// Every chunk was closed.
+    Complete,
+}
+
+impl Chunk {
+    /// The syntax error score of a corrupt line or the completion score
+    /// of an incomplete one.
+    pub fn score(&self) -> Num {
+        match self {
+            Self::Corrupt(a) => a.score(),
+            Self::Incomplete(symbols) =>
+                symbols.iter()
+                    .rev()
+                    .fold(Num::default(), |sum, sym|
+                        sum * 5 + match *sym {
+                            Parenthesis => 1,
+                            SquareBracket => 2,
+                            CurlyBrace => 3,
+                            AngleBracket => 4,
+                        }
+                    ),
+            _ => Num::default(),
+        }
+    }
+
+    /// Whether the line closes a bracket with the wrong kind.
+    pub fn is_corrupt(&self) -> bool {
+        matches!(self, Self::Corrupt(_))
+    }
+
+    /// Whether the line ends with brackets left open.
+    pub fn is_incomplete(&self) -> bool {
+        matches!(self, Self::Incomplete(_))
+    }
+}
+
+/// Check the brackets of one line.
+pub fn check_chunk(line: &str) -> anyhow::Result<Chunk> {
+    let symbols = line.chars()
+        .map(Symbol::from_char)
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut stack = vec![];
+
+    for sym in symbols {
+        match sym {
+            Open(a) => stack.push(a),
+            Close(a) =>
+                if let Some(b) = stack.pop() {
+                    if b != a {
+                        return Ok(Chunk::Corrupt(a));
+                    }
+                } else {
+                    return Ok(Chunk::Corrupt(a));
+                },
+        }
+    }
+
+    Ok(
+        if stack.is_empty() {
+            Chunk::Complete
+        } else {
+            Chunk::Incomplete(stack)
+        }
+    )
+}
+
+/// Parse and check every line.
+pub fn parse(input: &str) -> anyhow::Result<Vec<Chunk>> {
+    input
+        .lines()
+        .map(check_chunk)
+        .collect()
+}
+
+/// Part 1: the total syntax error score of the corrupt lines.
+pub fn part1(chunks: &[Chunk]) -> Num {
+    chunks.iter()
+        .filter(|chunk| chunk.is_corrupt())
+        .fold(Num::default(), |sum, chunk| sum + chunk.score())
+}
+
+/// Part 2: the middle completion score of the incomplete lines.
+pub fn part2(chunks: &[Chunk]) -> Num {
+    let mut scores: Vec<_> = chunks.iter()
+        .filter(|chunk| chunk.is_incomplete())
+        .map(|chunk| chunk.score())
+        .collect();
+    let len = scores.len();
+    *scores.select_nth_unstable(len / 2).1
+}
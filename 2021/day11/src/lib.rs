@@ -0,0 +1,187 @@
+//! Day 11: Dumbo Octopus.
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{bail, ensure};
+use aoc::num::Int;
+use tracing::{debug, trace};
+
+/// A number of flashes or steps.
+pub type Num = aoc::num::U32;
+
+/// The energy levels of the octopuses and the steps taken so far.
+#[derive(Debug)]
+pub struct Matrix {
+    data: Vec<u32>,
+    rows: usize,
+    cols: usize,
+    steps: Num,
+}
+
+impl FromStr for Matrix {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let common::Digits {data, rows, cols} = common::digits(s)?;
+
+        Ok(Matrix {data, rows, cols, steps: Num::default()})
+    }
+}
+
+impl fmt::Display for Matrix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.data.chunks(self.cols) {
+            for n in row {
+                write!(f, "{}", n)?;
+            }
+            writeln!(f)?;
+        }
+
+        fmt::Result::Ok(())
+    }
+}
+
+impl Matrix {
+    /// The number of steps taken so far.
+    pub fn steps(&self) -> Num {
+        self.steps
+    }
+
+    fn get_mut(&mut self, i: usize, j: usize) -> Option<&mut u32> {
+        if i < self.rows && j < self.cols {
+            self.data.get_mut(i * self.cols + j)
+        } else {
+            None
+        }
+    }
+
+    /// Flash the octopus at row `i`, column `j` if it has enough energy,
+    /// and the ones around it that flash in turn. Returns the number of
+    /// flashes.
+    pub fn flash(&mut self, i: usize, j: usize) -> Num {
+        let mut count = Num::default();
+
+        if let Some(n) = self.get_mut(i, j) {
+            if *n >= 9 {
+                count = Num::new(1);
+                *n = 0;
+
+                if i > 0 && j > 0 {
+                    count += self.flash(i - 1, j - 1);
+                }
+                if i > 0 {
+                    count += self.flash(i - 1, j);
+                    count += self.flash(i - 1, j + 1);
+                }
+                if j > 0 {
+                    count += self.flash(i, j - 1);
+                    count += self.flash(i + 1, j - 1);
+                }
+                count += self.flash(i + 1, j);
+                count += self.flash(i, j + 1);
+                count += self.flash(i + 1, j + 1);
+            } else if *n > 0 {
+                *n += 1;
+            }
+        }
+
+        count
+    }
+
+    /// Take one step and return the number of flashes.
+    pub fn step(&mut self) -> Num {
+        let mut count = Num::default();
+
+        self.steps += 1;
+        self.data.iter_mut().for_each(|n| *n += 1);
+        for i in 0 .. self.rows {
+            for j in 0 .. self.cols {
+                if let Some(10 .. ) = self.get_mut(i, j) {
+                    count += self.flash(i, j);
+                }
+            }
+        }
+
+        count
+    }
+
+    /// Take `n` steps, calling `inspect` after each, and return the number
+    /// of flashes.
+    pub fn step_n(&mut self, n: u32, mut inspect: impl FnMut(&Self)) -> Num {
+        let mut count = Num::default();
+
+        for _ in 0 .. n {
+            let flashes = self.step();
+            trace!(step = %self.steps, %flashes, "step");
+            inspect(self);
+            count += flashes;
+        }
+
+        count
+    }
+
+    /// Step until every octopus flashes at once, calling `inspect` after
+    /// each step, and return the total number of steps taken, including
+    /// earlier ones.
+    pub fn find_synch(&mut self, mut inspect: impl FnMut(&Self)) -> Num {
+        let max_flashes = Num::of(self.rows * self.cols);
+
+        loop {
+            let flashes = self.step();
+            inspect(self);
+
+            if max_flashes == flashes {
+                debug!(step = %self.steps, "all octopuses flashed");
+                break self.steps;
+            }
+        }
+    }
+}
+
+impl aoc::repl::Repl for Matrix {
+    const STEP: &'static str = "one tick of the octopuses' energy";
+    const QUERIES: &'static [(&'static str, &'static str)] = &[
+        ("steps", "steps taken so far"),
+        ("energy <r> <c>", "energy level of the octopus at row r, column c"),
+        ("synced", "whether every octopus flashed in the last step"),
+    ];
+
+    fn load(input: &str) -> anyhow::Result<Self> {
+        Self::from_str(input)
+    }
+
+    fn step(&mut self) -> anyhow::Result<String> {
+        let flashes = Matrix::step(self);
+
+        Ok(format!("Step {}: {} flashes", self.steps, flashes))
+    }
+
+    fn show(&self) -> String {
+        format!("After step {}:\n{}", self.steps, self)
+    }
+
+    fn query(&self, name: &str, args: &[&str]) -> anyhow::Result<String> {
+        match (name, args) {
+            ("steps", []) => Ok(self.steps.to_string()),
+            ("energy", [i, j]) => {
+                let (i, j): (usize, usize) = (i.parse()?, j.parse()?);
+                ensure!(i < self.rows && j < self.cols, "No octopus at {}, {}", i, j);
+                Ok(self.data[i * self.cols + j].to_string())
+            },
+            ("synced", []) => Ok(self.data.iter().all(|&n| n == 0).to_string()),
+            _ => bail!("Unknown query: {} {}", name, args.join(" ")),
+        }
+    }
+}
+
+/// Part 1: the number of flashes in the first 100 steps.
+pub fn part1(matrix: &mut Matrix, inspect: impl FnMut(&Matrix)) -> Num {
+    matrix.step_n(100, inspect)
+}
+
+/// Part 2: the first step on which every octopus flashes, continuing from
+/// where [`part1`] left the octopuses.
+pub fn part2(matrix: &mut Matrix, inspect: impl FnMut(&Matrix)) -> Num {
+    matrix.find_synch(inspect)
+}
@@ -0,0 +1,237 @@
+//! Day 13: Transparent Origami.
+
+use std::fmt;
+use std::str::FromStr;
+use std::collections::BTreeSet;
+
+use anyhow::{bail, Context, ensure};
+use aoc::num::Int;
+use tracing::debug;
+
+/// A coordinate on the paper.
+pub type Num = aoc::num::U32;
+
+/// The dots on the transparent paper, which prints as the dots it shows.
+#[derive(Debug, Default)]
+pub struct Paper {
+    points: BTreeSet<Point>,
+    max_width: Num,
+}
+
+/// A dot, ordered by row first to print the paper line by line.
+#[derive(Debug, PartialEq, PartialOrd, Eq, Ord)]
+pub struct Point {
+    /// Row, counted down from the top.
+    pub y: Num,
+    /// Column, counted from the left.
+    pub x: Num,
+}
+
+impl FromStr for Paper {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let mut points = BTreeSet::new();
+        let mut max_width = Num::default();
+
+        for line in s.lines() {
+            let (x, y) = line.split_once(',')
+                .with_context(|| format!("Invalid point: {}", line))?;
+            let x: Num = str::parse(x)?;
+            let y: Num = str::parse(y)?;
+
+            points.insert(Point {x, y});
+
+            if x > max_width {
+                max_width = x;
+            }
+        }
+
+        Ok(Self {points, max_width})
+    }
+}
+
+impl fmt::Display for Paper {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut last_x = Num::default();
+        let mut last_y = Num::default();
+        for point in &self.points {
+            if point.y > last_y {
+                if !f.fill().is_ascii_whitespace() {
+                    for _ in 0 .. (point.y - last_y).get() {
+                        for _ in 0 .. (self.max_width + 1 - last_x).get() {
+                            write!(f, "{}", f.fill())?;
+                        }
+                        writeln!(f)?;
+
+                        last_x = Num::default();
+                    }
+                } else {
+                    write!(f, "{:\n<1$}", "", (point.y - last_y).get() as usize)?;
+                }
+
+                last_y = point.y;
+                last_x = Num::default();
+            }
+
+            for _ in 0 .. (point.x - last_x).get() {
+                write!(f, "{}", f.fill())?;
+            }
+            write!(f, "#")?;
+
+            last_x = point.x + 1;
+        }
+
+        fmt::Result::Ok(())
+    }
+}
+
+/// A fold along a vertical (x) or horizontal (y) line.
+#[derive(Debug)]
+pub enum Fold {
+    /// `fold along x=N`, folding the right half over to the left.
+    X(Num),
+    /// `fold along y=N`, folding the bottom half up.
+    Y(Num),
+}
+
+impl FromStr for Fold {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        ensure!(s.starts_with("fold along "), "Invalid fold: {}", s);
+
+        let fold_type = s.get(11 .. 13)
+            .with_context(|| format!("Invalid fold: {}", s))?;
+
+        let num: Num = str::parse(s.get(13..)
+            .with_context(|| format!("Invalid fold: {}", s))?)?;
+
+        Ok(match fold_type {
+            "x=" => Self::X(num),
+            "y=" => Self::Y(num),
+            _ => bail!("Invalid fold: {}", s)
+        })
+    }
+}
+
+impl Paper {
+    /// The number of dots visible.
+    pub fn dots(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Fold the paper along each of `folds` in turn.
+    pub fn fold(self, folds: &[Fold]) -> Self {
+        let mut points = self.points;
+        // The widest the paper gets after folding left, if it ever does
+        let mut folded_width: Option<Num> = None;
+
+        let fold_num = |curr, fold_num| {
+            if curr > fold_num {
+                fold_num * 2 - curr
+            } else {
+                curr
+            }
+        };
+
+        for fold in folds {
+            points = points.into_iter()
+                .map(|p| match *fold {
+                    Fold::X(n) => {
+                        let x = fold_num(p.x, n);
+                        folded_width = folded_width.max(Some(x));
+
+                        Point{x, ..p}
+                    },
+                    Fold::Y(n) => Point{y: fold_num(p.y, n), ..p},
+                })
+                .collect();
+            debug!(?fold, dots = points.len(), "fold applied");
+        }
+
+        let max_width = folded_width.unwrap_or(self.max_width);
+        debug!(points = points.len(), %max_width, "paper folded");
+
+        Self {points, max_width}
+    }
+}
+
+/// Parse the dots and the fold instructions.
+pub fn parse(input: &str) -> anyhow::Result<(Paper, Vec<Fold>)> {
+    let (paper, folds) = input
+        .split_once("\n\n").context("Invalid input")?;
+    let paper: Paper = str::parse(paper)?;
+    let folds = folds.lines()
+        .map(str::parse::<Fold>)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    anyhow::Ok((paper, folds))
+}
+
+/// The paper with the folds still to make, stepped through in the REPL
+/// one fold at a time.
+pub struct Origami {
+    paper: Paper,
+    folds: Vec<Fold>,
+    done: usize,
+}
+
+impl aoc::repl::Repl for Origami {
+    const STEP: &'static str = "the next fold of the instructions";
+    const QUERIES: &'static [(&'static str, &'static str)] = &[
+        ("dots", "number of visible dots"),
+        ("folds", "folds made and left to make"),
+        ("next", "the next fold"),
+    ];
+
+    fn load(input: &str) -> anyhow::Result<Self> {
+        let (paper, folds) = parse(input)?;
+
+        Ok(Self {paper, folds, done: 0})
+    }
+
+    fn step(&mut self) -> anyhow::Result<String> {
+        let fold = self.done .. self.done + 1;
+        ensure!(fold.end <= self.folds.len(), "No folds left");
+
+        self.paper = std::mem::take(&mut self.paper).fold(&self.folds[fold]);
+        self.done += 1;
+
+        Ok(format!(
+            "Fold {}: {:?}, {} dots",
+            self.done,
+            self.folds[self.done - 1],
+            self.paper.dots(),
+        ))
+    }
+
+    fn show(&self) -> String {
+        self.paper.to_string()
+    }
+
+    fn query(&self, name: &str, args: &[&str]) -> anyhow::Result<String> {
+        match (name, args) {
+            ("dots", []) => Ok(self.paper.dots().to_string()),
+            ("folds", []) => Ok(format!(
+                "{} made, {} left",
+                self.done,
+                self.folds.len() - self.done,
+            )),
+            ("next", []) => Ok(self.folds.get(self.done)
+                .map_or(String::from("none"), |fold| format!("{:?}", fold))),
+            _ => bail!("Unknown query: {} {}", name, args.join(" ")),
+        }
+    }
+}
+
+/// Part 1: the paper after the first fold, the answer being its number of
+/// dots.
+pub fn part1(paper: Paper, folds: &[Fold]) -> Paper {
+    paper.fold(&folds[0..1])
+}
+
+/// Part 2: the paper after the remaining folds, which shows the code.
+pub fn part2(paper: Paper, folds: &[Fold]) -> Paper {
+    paper.fold(&folds[1..])
+}
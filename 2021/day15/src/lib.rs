@@ -0,0 +1,224 @@
+//! Day 15: Chiton.
+
+use std::str::FromStr;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use aoc::num::Int;
+use tracing::{debug, trace};
+
+/// A risk level.
+pub type Num = aoc::num::U32;
+
+#[derive(PartialEq, Eq)]
+struct Risk {
+    risk: Num,
+    pos: (usize, usize),
+}
+
+// Invert the results of a compare for Risk so that
+// the BinaryHeap starts with the smallest value
+impl Ord for Risk {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .risk
+            .cmp(&self.risk)
+            .then_with(|| self.pos.cmp(&other.pos))
+    }
+}
+
+impl PartialOrd for Risk {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// A grid of values stored row by row.
+#[derive(Debug)]
+pub struct Matrix<T> {
+    data: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+impl FromStr for Matrix<Num> {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let common::Digits {data, rows, cols} = common::digits(s)?;
+        let data = data.into_iter().map(|d| Num::of(d)).collect();
+
+        Ok(Matrix {data, rows, cols})
+    }
+}
+
+impl<T> Matrix<T> {
+    /// The value at row `i`, column `j`, if there is one.
+    pub fn get(&self, i: usize, j: usize) -> Option<&T> {
+        if i < self.rows && j < self.cols {
+            self.data.get(i * self.cols + j)
+        } else {
+            None
+        }
+    }
+
+    /// The value at row `i`, column `j` to change, if there is one.
+    pub fn get_mut(&mut self, i: usize, j: usize) -> Option<&mut T> {
+        if i < self.rows && j < self.cols {
+            self.data.get_mut(i * self.cols + j)
+        } else {
+            None
+        }
+    }
+
+    /// The value at row `i`, column `j`, panicking outside the grid.
+    pub fn at(&self, i: usize, j: usize) -> &T {
+        if i < self.rows && j < self.cols {
+            &self.data[i * self.cols + j]
+        } else {
+            panic!("Invalid coordinates: ({}, {})", i, j)
+        }
+    }
+
+    /// Replace the value at row `i`, column `j`.
+    pub fn set(&mut self, i: usize, j: usize, value: T) {
+        self.data[i * self.cols + j] = value;
+    }
+
+    /// The positions above, left, below and right of row `i`, column `j`
+    /// that are inside the grid.
+    pub fn neighbors(&self, i: usize, j: usize) -> Vec<(usize, usize)> {
+        let mut neighbors = vec![];
+
+        if i > 0 {
+            neighbors.push((i - 1, j));
+        }
+        if j > 0 {
+            neighbors.push((i, j - 1));
+        }
+        if i < self.rows - 1 {
+            neighbors.push((i + 1, j));
+        }
+        if j < self.cols - 1 {
+            neighbors.push((i, j + 1));
+        }
+
+        neighbors
+    }
+}
+
+impl Matrix<Num> {
+    /// A grid with every position set to `value`.
+    pub fn filled(rows: usize, cols: usize, value: Num) -> Self {
+        Self {data: vec![value; rows * cols], rows, cols}
+    }
+
+    /// The lowest total risk to reach every position, assuming the path
+    /// only moves down and right, which does not hold for every map.
+    pub fn paths_down_right(&self) -> Self {
+        let mut risks = Self::filled(self.rows, self.cols, Num::default());
+
+        for j in 1 .. self.cols {
+            let risk = risks.at(0, j - 1) + self.at(0, j);
+            let curr = risks.get_mut(0, j).unwrap();
+            *curr = risk;
+        }
+        for i in 1 .. self.rows {
+            let risk = risks.at(i - 1, 0) + self.at(i, 0);
+            let curr = risks.get_mut(i, 0).unwrap();
+            *curr = risk;
+        }
+        for i in 1 .. self.rows {
+            for j in 1 .. self.cols {
+                let a = risks.get(i - 1, j).copied();
+                let b = risks.get(i, j - 1).copied();
+                let risk = self.at(i, j);
+                let curr = risks.get_mut(i, j).unwrap();
+
+                *curr = if let Some(a) = a {
+                    if let Some(b) = b {
+                        a.min(b) + risk
+                    } else {
+                        a + risk
+                    }
+                } else {
+                    b.unwrap() + risk
+                }
+            }
+        }
+
+        risks
+    }
+
+    /// The lowest total risk from the top left to the bottom right, found
+    /// with Dijkstra's algorithm.
+    pub fn paths(&self) -> Num {
+        let mut dist = Self::filled(self.rows, self.cols, Num::MAX);
+
+        let mut heap = BinaryHeap::new();
+
+        dist.set(0, 0, Num::default());
+        heap.push(Risk {
+            risk: Num::default(),
+            pos: (0, 0),
+        });
+
+        while let Some(Risk {risk, pos}) = heap.pop() {
+            if pos == (self.rows - 1, self.cols - 1) {
+                debug!(%risk, rows = self.rows, cols = self.cols, "reached exit");
+                return risk;
+            }
+
+            if risk > *dist.at(pos.0, pos.1) {
+                continue;
+            }
+            trace!(row = pos.0, col = pos.1, %risk, "node settled");
+
+            for neighbor in self.neighbors(pos.0, pos.1) {
+                let risk = risk + self.at(neighbor.0, neighbor.1);
+
+                if risk < *dist.at(neighbor.0, neighbor.1) {
+                    heap.push(Risk {risk, pos: neighbor});
+                    dist.set(neighbor.0, neighbor.1, risk);
+                }
+            }
+        }
+
+        Num::default()
+    }
+
+    /// The full map, made of the tile repeated `factor` times each way with
+    /// the risk rising by one per repetition.
+    pub fn multi_map(&self, factor: usize) -> Self {
+        let rows = self.rows * factor;
+        let cols = self.cols * factor;
+        let mut data = Vec::with_capacity(rows * cols);
+
+        for i in 0 .. rows {
+            for j in 0 .. cols {
+                let old = self.at(i % self.rows, j % self.cols);
+                let modifier = Num::of(i / self.rows + j / self.cols);
+                data.push((old + modifier - 1) % 9 + 1);
+            }
+        }
+
+        Self {data, rows, cols}
+    }
+}
+
+/// The lowest total risk from the top left to the bottom right using
+/// [`Matrix::paths_down_right`].
+pub fn down_right(map: &Matrix<Num>) -> Num {
+    let risks = map.paths_down_right();
+    *risks.at(risks.rows - 1, risks.cols - 1)
+}
+
+/// Part 1: the lowest total risk through the tile.
+pub fn part1(map: &Matrix<Num>) -> Num {
+    map.paths()
+}
+
+/// Part 2: the lowest total risk through the full map, five tiles each way.
+pub fn part2(map: &Matrix<Num>) -> Num {
+    map.multi_map(5).paths()
+}
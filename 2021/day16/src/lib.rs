@@ -0,0 +1,245 @@
+//! Day 16: Packet Decoder.
+
+use anyhow::{Context};
+use aoc::num::Int;
+use tracing::trace;
+
+/// The value of a packet.
+pub type Num = aoc::num::U64;
+
+/// A decoded packet, either a literal value or an operator over
+/// sub-packets.
+#[derive(Debug)]
+pub enum Operator {
+    /// A packet combining the values of its sub-packets.
+    Operator {
+        /// The packet version from its header.
+        version: u32,
+        /// What it computes, from the type ID in its header.
+        type_id: OpType,
+        /// The sub-packets, in order.
+        packets: Vec<Operator>,
+    },
+    /// A packet of type ID 4, holding a number.
+    Literal {
+        /// The packet version from its header.
+        version: u32,
+        /// The number, from its groups of four bits.
+        value: Num,
+    },
+}
+
+/// What an operator packet computes from its sub-packets.
+#[derive(Debug)]
+pub enum OpType {
+    /// Type ID 0, the sum of the values.
+    Sum,
+    /// Type ID 1, the product of the values.
+    Prod,
+    /// Type ID 2, the smallest value.
+    Min,
+    /// Type ID 3, the largest value.
+    Max,
+    /// Type ID 5, 1 if the first of two values is greater, else 0.
+    Gt,
+    /// Type ID 6, 1 if the first of two values is less, else 0.
+    Lt,
+    /// Type ID 7, 1 if both values are equal, else 0.
+    Eq,
+}
+
+fn to_num(b: bool) -> Num {
+    Num::new(if b { 1 } else { 0 })
+}
+
+impl OpType {
+    /// The operation for a packet type ID, panicking on 4 (a literal) and
+    /// unknown IDs.
+    pub fn from_num(num: u32) -> Self {
+        match num {
+            0 => Self::Sum,
+            1 => Self::Prod,
+            2 => Self::Min,
+            3 => Self::Max,
+            5 => Self::Gt,
+            6 => Self::Lt,
+            7 => Self::Eq,
+            _ => panic!("Invalid type id: {}", num)
+        }
+    }
+
+    /// Apply the operation to the values of `packets`.
+    pub fn evaluate(&self, packets: &[Operator]) -> Num {
+        let mut iter = packets.iter().map(|p| p.evaluate());
+        match self {
+            Self::Sum => iter.sum(),
+            Self::Prod => iter.product(),
+            Self::Min => iter.min().unwrap(),
+            Self::Max => iter.max().unwrap(),
+            Self::Gt => to_num(iter.next().unwrap() > iter.next().unwrap()),
+            Self::Lt => to_num(iter.next().unwrap() < iter.next().unwrap()),
+            Self::Eq => to_num(iter.next().unwrap() == iter.next().unwrap()),
+        }
+    }
+}
+
+/// One bit of the transmission.
+#[derive(Debug)]
+pub enum Binary {
+    /// A 0 bit.
+    Zero,
+    /// A 1 bit.
+    One,
+}
+
+impl Binary {
+    /// The bits of a hexadecimal transmission.
+    pub fn from_hex(s: &str) -> anyhow::Result<Vec<Self>> {
+        let mut digits = Vec::with_capacity(s.len() * 4);
+
+        for c in s.chars() {
+            let digit = c.to_digit(16)
+                .with_context(|| format!("Invalid digit: {}", c))?;
+            digits.push(Self::from_bit(digit & 0b1000 == 0b1000));
+            digits.push(Self::from_bit(digit & 0b0100 == 0b0100));
+            digits.push(Self::from_bit(digit & 0b0010 == 0b0010));
+            digits.push(Self::from_bit(digit & 0b0001 == 0b0001));
+        }
+
+        anyhow::Ok(digits)
+    }
+
+    fn from_bit(bit: bool) -> Self {
+        if bit {
+            Self::One
+        } else {
+            Self::Zero
+        }
+    }
+
+    fn num(&self) -> u32 {
+        match self {
+            Self::One => 1,
+            Self::Zero => 0,
+        }
+    }
+}
+
+fn from_bin(bin: &[Binary]) -> u32 {
+    bin.iter().fold(0, |sum, n| sum * 2 + n.num())
+}
+
+fn get_value(bin: &[Binary]) -> (usize, Num) {
+    let mut sum = Num::default();
+    let mut count = 0;
+
+    for b in bin.chunks(5) {
+        sum *= 16;
+        sum += Num::of(from_bin(&b[1..5]));
+        count += 5;
+
+        if let Binary::Zero = b[0] {
+            break;
+        }
+    }
+
+    (count, sum)
+}
+
+fn get_packets(bin: &[Binary]) -> (usize, Vec<Operator>) {
+    let mut packets = vec![];
+    let mut curr_len;
+
+    if let Binary::One = bin[0] {
+        let size = from_bin(&bin[1..12]) as usize;
+        packets.reserve(size);
+        curr_len = 12;
+
+        for _ in 0 .. size {
+            let (len, packet) = Operator::from_binary(&bin[curr_len..]);
+            curr_len += len;
+            packets.push(packet);
+        }
+    } else {
+        let bits = from_bin(&bin[1..16]) as usize;
+        curr_len = 16;
+
+        while curr_len - 16 < bits {
+            let (len, packet) = Operator::from_binary(&bin[curr_len..16+bits]);
+            curr_len += len;
+            packets.push(packet);
+        }
+    }
+
+    (curr_len, packets)
+}
+
+impl Operator {
+    /// Decode the packet at the start of `bin`, returning how many bits
+    /// it took up.
+    pub fn from_binary(bin: &[Binary]) -> (usize, Self) {
+        let version = from_bin(&bin[0..3]);
+        let type_id = from_bin(&bin[3..6]);
+
+        match type_id {
+            4 => {
+                let (len, value) = get_value(&bin[6..]);
+                trace!(version, %value, bits = len + 6, "literal decoded");
+                (len + 6, Self::Literal {
+                    version,
+                    value,
+                })
+            },
+            _ => {
+                let (len, packets) = get_packets(&bin[6..]);
+                trace!(
+                    version,
+                    type_id,
+                    packets = packets.len(),
+                    bits = len + 6,
+                    "operator decoded",
+                );
+                (len + 6, Self::Operator {
+                    version,
+                    type_id: OpType::from_num(type_id),
+                    packets,
+                })
+            }
+        }
+    }
+
+    /// The sum of the versions of this packet and all packets inside it.
+    pub fn sum_version(&self) -> u32 {
+        match self {
+            Self::Literal {version, ..} => *version,
+            Self::Operator {version, packets, ..} => *version
+                + packets.iter().fold(0, |sum, p| sum + p.sum_version()),
+        }
+    }
+
+    /// The value of the expression the packet stands for.
+    pub fn evaluate(&self) -> Num {
+        match self {
+            Self::Literal {value, ..} => *value,
+            Self::Operator {type_id, packets, ..} =>
+                type_id.evaluate(packets),
+        }
+    }
+}
+
+/// Decode the outermost packet of a hexadecimal transmission.
+pub fn parse(input: &str) -> anyhow::Result<Operator> {
+    let binary = Binary::from_hex(input.trim())?;
+
+    anyhow::Ok(Operator::from_binary(&binary).1)
+}
+
+/// Part 1: the sum of the version numbers of every packet.
+pub fn part1(packet: &Operator) -> u32 {
+    packet.sum_version()
+}
+
+/// Part 2: the value of the outermost packet.
+pub fn part2(packet: &Operator) -> Num {
+    packet.evaluate()
+}
@@ -0,0 +1,113 @@
+//! Day 17: Trick Shot.
+
+use anyhow::Context;
+use aoc::num::Int;
+
+/// A coordinate or velocity.
+pub type Num = aoc::num::I32;
+
+/// A position, or a velocity in steps of one position.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Point {
+    /// Forward, away from the submarine.
+    pub x: Num,
+    /// Up, with depths negative.
+    pub y: Num,
+}
+
+impl Point {
+    /// Whether the position lies in the area between `min` and `max`.
+    pub fn in_range(&self, min: &Point, max: &Point) -> bool {
+        self.x >= min.x && self.x <= max.x
+            && self.y >= min.y && self.y <= max.y
+    }
+
+    /// Whether a probe launched with this velocity is ever in the target
+    /// area between `min` and `max`.
+    pub fn hit_target(&self, min: &Point, max: &Point) -> bool {
+        let mut vel = Point {x: self.x, y: self.y};
+        let mut pos = Point {x: Num::default(), y: Num::default()};
+        while pos.x <= max.x && pos.y >= min.y {
+            pos.x += vel.x;
+            pos.y += vel.y;
+
+            if vel.x > 0 {
+                vel.x -= 1;
+            }
+            vel.y -= 1;
+
+            if pos.in_range(min, max) {
+                return true;
+            }
+        }
+
+        false
+    }
+}
+
+/// Parse the target area into its lowest and highest corner.
+pub fn parse_area(s: &str) -> anyhow::Result<(Point, Point)> {
+    let s = s.trim().strip_prefix("target area: x=")
+        .context("Missing start of target area string")?;
+    let (x, y) = s.split_once(", y=")
+        .context("Missing middle of target area string")?;
+
+    let parse_range = |s: &str| {
+        let (s1, s2) = s.split_once("..")
+            .with_context(|| format!("Invalid range: {}", s))?;
+        anyhow::Ok((str::parse::<Num>(s1)?, str::parse::<Num>(s2)?))
+    };
+
+    let x = parse_range(x)?;
+    let y = parse_range(y)?;
+
+    anyhow::Ok((
+        Point {
+            x: x.0.min(x.1),
+            y: y.0.min(y.1),
+        },
+        Point {
+            x: x.0.max(x.1),
+            y: y.0.max(y.1),
+        },
+    ))
+}
+
+/// Every initial velocity that hits the target area between `min` and
+/// `max`.
+pub fn get_possible(min: &Point, max: &Point) -> Vec<Point> {
+    let v_min = Point {
+        x: (Num::new(((min.x * 8 + 1).get() as f64).sqrt() as _) - 1) / 2,
+        y: min.y,
+    };
+    let v_max = Point {
+        x: max.x,
+        y: -min.y,
+    };
+
+    let mut vels = vec![];
+    for x in v_min.x.get() ..= v_max.x.get() {
+        for y in v_min.y.get() ..= v_max.y.get() {
+            let curr = Point {x: Num::new(x), y: Num::new(y)};
+            if curr.hit_target(min, max) {
+                vels.push(curr);
+            }
+        }
+    }
+
+    vels
+}
+
+/// Part 1: the highest position a probe reaching the target gets to.
+///
+/// The probe comes back down through y = 0 at the speed it went up with,
+/// so the fastest launch that still hits the bottom row climbs the
+/// triangle number of one less than its depth.
+pub fn part1(min: &Point) -> Num {
+    min.y * (min.y + 1) / 2
+}
+
+/// Part 2: the number of initial velocities that hit the target.
+pub fn part2(min: &Point, max: &Point) -> usize {
+    get_possible(min, max).len()
+}
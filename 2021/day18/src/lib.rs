@@ -0,0 +1,234 @@
+//! Day 18: Snailfish.
+
+use std::fmt;
+use std::ops::Add;
+use std::str::FromStr;
+
+use anyhow::{bail, Context};
+use aoc::num::Int;
+use tracing::trace;
+
+/// The magnitude of a snailfish number.
+pub type Num = aoc::num::U32;
+
+/// A snailfish number: a regular number or a pair of snailfish numbers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Snailfish {
+    /// A plain number.
+    Regular(u32),
+    /// `[left,right]`.
+    Pair(Box<Snailfish>, Box<Snailfish>),
+}
+
+// Parse the number at the start of `s`, returning what is left after it
+fn parse_prefix(s: &str) -> anyhow::Result<(Snailfish, &str)> {
+    if let Some(rest) = s.strip_prefix('[') {
+        let (left, rest) = parse_prefix(rest)?;
+        let rest = rest.strip_prefix(',')
+            .with_context(|| format!("Expected a comma at: {}", rest))?;
+        let (right, rest) = parse_prefix(rest)?;
+        let rest = rest.strip_prefix(']')
+            .with_context(|| format!("Expected a closing bracket at: {}", rest))?;
+
+        return Ok((Snailfish::Pair(Box::new(left), Box::new(right)), rest));
+    }
+
+    let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    if end == 0 {
+        bail!("Expected a number at: {}", s);
+    }
+
+    Ok((Snailfish::Regular(s[.. end].parse()?), &s[end ..]))
+}
+
+impl FromStr for Snailfish {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let (number, rest) = parse_prefix(s.trim())?;
+        if !rest.is_empty() {
+            bail!("Trailing characters after number: {}", rest);
+        }
+
+        Ok(number)
+    }
+}
+
+impl fmt::Display for Snailfish {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Regular(n) => write!(f, "{}", n),
+            Self::Pair(left, right) => write!(f, "[{},{}]", left, right),
+        }
+    }
+}
+
+/// Snailfish addition: pair up both numbers and reduce the result.
+///
+/// ```
+/// # use day18_2021::Snailfish;
+/// let a: Snailfish = "[[[[4,3],4],4],[7,[[8,4],9]]]".parse()?;
+/// let b: Snailfish = "[1,1]".parse()?;
+///
+/// assert_eq!((a + b).to_string(), "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]");
+/// # anyhow::Ok(())
+/// ```
+impl Add for Snailfish {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        let mut sum = Self::Pair(Box::new(self), Box::new(rhs));
+        sum.reduce();
+
+        sum
+    }
+}
+
+impl Snailfish {
+    fn add_leftmost(&mut self, value: u32) {
+        match self {
+            Self::Regular(n) => *n += value,
+            Self::Pair(left, _) => left.add_leftmost(value),
+        }
+    }
+
+    fn add_rightmost(&mut self, value: u32) {
+        match self {
+            Self::Regular(n) => *n += value,
+            Self::Pair(_, right) => right.add_rightmost(value),
+        }
+    }
+
+    // Explode the leftmost pair nested inside four pairs, returning the
+    // values still to be added to the regular numbers on either side
+    fn explode_at(&mut self, depth: usize) -> Option<(u32, u32)> {
+        let Self::Pair(left, right) = self else {
+            return None;
+        };
+
+        if depth >= 4 {
+            if let (Self::Regular(l), Self::Regular(r)) = (&**left, &**right) {
+                let carry = (*l, *r);
+                *self = Self::Regular(0);
+                return Some(carry);
+            }
+        }
+
+        if let Some((l, r)) = left.explode_at(depth + 1) {
+            right.add_leftmost(r);
+            return Some((l, 0));
+        }
+        if let Some((l, r)) = right.explode_at(depth + 1) {
+            left.add_rightmost(l);
+            return Some((0, r));
+        }
+
+        None
+    }
+
+    /// Explode the leftmost pair nested inside four pairs, if there is one.
+    ///
+    /// ```
+    /// # use day18_2021::Snailfish;
+    /// let mut n: Snailfish = "[[3,[2,[1,[7,3]]]],[6,[5,[4,[3,2]]]]]".parse()?;
+    ///
+    /// assert!(n.explode());
+    /// assert_eq!(n.to_string(), "[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]");
+    /// assert!(n.explode());
+    /// assert_eq!(n.to_string(), "[[3,[2,[8,0]]],[9,[5,[7,0]]]]");
+    /// assert!(!n.explode());
+    /// # anyhow::Ok(())
+    /// ```
+    pub fn explode(&mut self) -> bool {
+        self.explode_at(0).is_some()
+    }
+
+    /// Split the leftmost regular number of 10 or more, if there is one.
+    ///
+    /// ```
+    /// # use day18_2021::Snailfish;
+    /// let mut n: Snailfish = "[[[[0,7],4],[15,[0,13]]],[1,1]]".parse()?;
+    ///
+    /// assert!(n.split());
+    /// assert_eq!(n.to_string(), "[[[[0,7],4],[[7,8],[0,13]]],[1,1]]");
+    /// assert!(n.split());
+    /// assert_eq!(n.to_string(), "[[[[0,7],4],[[7,8],[0,[6,7]]]],[1,1]]");
+    /// assert!(!n.split());
+    /// # anyhow::Ok(())
+    /// ```
+    pub fn split(&mut self) -> bool {
+        match self {
+            Self::Regular(n) if *n >= 10 => {
+                *self = Self::Pair(
+                    Box::new(Self::Regular(*n / 2)),
+                    Box::new(Self::Regular(*n - *n / 2)),
+                );
+                true
+            },
+            Self::Regular(_) => false,
+            Self::Pair(left, right) => left.split() || right.split(),
+        }
+    }
+
+    /// Explode and split until neither applies, always exploding first.
+    pub fn reduce(&mut self) {
+        loop {
+            if self.explode() {
+                trace!(number = %self, "after explode");
+            } else if self.split() {
+                trace!(number = %self, "after split");
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Three times the magnitude of the left element plus twice that of
+    /// the right, recursively.
+    ///
+    /// ```
+    /// # use day18_2021::Snailfish;
+    /// let n: Snailfish = "[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]".parse()?;
+    ///
+    /// assert_eq!(n.magnitude(), 3488);
+    /// # anyhow::Ok(())
+    /// ```
+    pub fn magnitude(&self) -> Num {
+        match self {
+            Self::Regular(n) => Num::of(*n),
+            Self::Pair(left, right) => left.magnitude() * 3 + right.magnitude() * 2,
+        }
+    }
+}
+
+/// One snailfish number per line of the homework.
+pub fn parse(input: &str) -> anyhow::Result<Vec<Snailfish>> {
+    input.lines()
+        .map(|line| line.parse()
+            .with_context(|| format!("Invalid number: {}", line)))
+        .collect()
+}
+
+/// Part 1: the magnitude of the sum of every number, in order.
+pub fn part1(numbers: &[Snailfish]) -> Num {
+    numbers.iter()
+        .cloned()
+        .reduce(|sum, n| sum + n)
+        .map_or(Num::default(), |sum| sum.magnitude())
+}
+
+/// Part 2: the largest magnitude of the sum of two different numbers,
+/// trying both orders since addition does not commute.
+pub fn part2(numbers: &[Snailfish]) -> Num {
+    let mut max = Num::default();
+
+    for (i, a) in numbers.iter().enumerate() {
+        for (j, b) in numbers.iter().enumerate() {
+            if i != j {
+                max = max.max((a.clone() + b.clone()).magnitude());
+            }
+        }
+    }
+
+    max
+}
@@ -0,0 +1,16 @@
+//! Runs day 18, Snailfish, on an input and prints the answers.
+
+fn main() -> anyhow::Result<()> {
+    let args = aoc::init();
+
+    let input = args.input(aoc::puzzle!())?;
+    let numbers = aoc::phase("parse", || day18_2021::parse(&input))?;
+
+    // Part 1
+    println!("{}", aoc::phase("part1", || day18_2021::part1(&numbers)));
+
+    // Part 2
+    println!("{}", aoc::phase("part2", || day18_2021::part2(&numbers)));
+
+    Ok(())
+}
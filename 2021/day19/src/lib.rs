@@ -0,0 +1,288 @@
+//! Day 19: Beacon Scanner.
+
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use anyhow::{bail, Context};
+use aoc::num::Int;
+use tracing::debug;
+
+/// A Manhattan distance between scanners.
+pub type Num = aoc::num::I32;
+
+/// A position relative to a scanner, or to scanner 0 once aligned.
+pub type Pos = [i32; 3];
+
+/// A rotation, as the matrix that turns a scanner's coordinates into
+/// the aligned ones.
+pub type Rotation = [[i32; 3]; 3];
+
+/// Scanners that see the same 12 beacons share at least this many of the
+/// distances between them.
+const OVERLAP: usize = 12;
+const SHARED_DISTANCES: usize = OVERLAP * (OVERLAP - 1) / 2;
+
+/// The beacons one scanner reports.
+#[derive(Debug, Clone)]
+pub struct Scanner {
+    /// Beacon positions relative to the scanner, in its own orientation.
+    pub beacons: Vec<Pos>,
+    // Squared distances from each beacon to the others, sorted. They do
+    // not depend on the scanner's orientation, so two beacons can only be
+    // the same one when they share at least 11 of them
+    neighbours: Vec<Vec<i32>>,
+    // All of the above together, which rules out most pairs of scanners
+    // before trying any rotation
+    distances: Vec<i32>,
+}
+
+// How many values two sorted lists have in common
+fn shared(a: &[i32], b: &[i32]) -> usize {
+    let (mut i, mut j, mut shared) = (0, 0, 0);
+
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            Ordering::Less => i += 1,
+            Ordering::Greater => j += 1,
+            Ordering::Equal => {
+                shared += 1;
+                i += 1;
+                j += 1;
+            },
+        }
+    }
+
+    shared
+}
+
+impl Scanner {
+    /// A scanner reporting `beacons`, with the distances between them
+    /// worked out for matching.
+    pub fn new(beacons: Vec<Pos>) -> Self {
+        let neighbours: Vec<Vec<i32>> = beacons.iter()
+            .map(|a| {
+                let mut distances: Vec<i32> = beacons.iter()
+                    .filter(|b| *b != a)
+                    .map(|b| (0 .. 3).map(|k| (a[k] - b[k]).pow(2)).sum())
+                    .collect();
+                distances.sort_unstable();
+                distances
+            })
+            .collect();
+
+        // Once per pair, keeping different pairs that happen to be as far
+        // apart
+        let mut distances: Vec<i32> = beacons.iter()
+            .enumerate()
+            .flat_map(|(i, a)| beacons[i + 1 ..].iter()
+                .map(move |b| (0 .. 3).map(|k| (a[k] - b[k]).pow(2)).sum()))
+            .collect();
+        distances.sort_unstable();
+
+        Self {beacons, neighbours, distances}
+    }
+}
+
+/// The 24 rotations a scanner may be in: every signed permutation of the
+/// axes that keeps the coordinate system right-handed.
+///
+/// ```
+/// use std::collections::HashSet;
+///
+/// let rotations = day19_2021::rotations();
+/// // A point with three different coordinates ends up somewhere else
+/// // under each of them
+/// let turned: HashSet<_> = rotations.iter()
+///     .map(|r| r.map(|row| row[0] + row[1] * 2 + row[2] * 3))
+///     .collect();
+/// assert_eq!(turned.len(), 24);
+///
+/// // None of them mirrors
+/// for [a, b, c] in rotations {
+///     let det = a[0] * (b[1] * c[2] - b[2] * c[1])
+///         - a[1] * (b[0] * c[2] - b[2] * c[0])
+///         + a[2] * (b[0] * c[1] - b[1] * c[0]);
+///     assert_eq!(det, 1);
+/// }
+/// ```
+pub fn rotations() -> Vec<Rotation> {
+    let perms = [[0, 1, 2], [0, 2, 1], [1, 0, 2], [1, 2, 0], [2, 0, 1], [2, 1, 0]];
+    let mut rotations = vec![];
+
+    for perm in perms {
+        // Swapping two axes mirrors, which an odd number of negated
+        // axes undoes
+        let odd = matches!(perm, [0, 2, 1] | [1, 0, 2] | [2, 1, 0]);
+
+        for signs in 0 .. 8 {
+            let sign = |k: usize| if signs & (1 << k) == 0 { 1 } else { -1 };
+            let negated = (signs as u32).count_ones() % 2 == 1;
+            if negated != odd {
+                continue;
+            }
+
+            let mut rotation = [[0; 3]; 3];
+            for (row, &col) in perm.iter().enumerate() {
+                rotation[row][col] = sign(row);
+            }
+            rotations.push(rotation);
+        }
+    }
+
+    rotations
+}
+
+fn rotate(rotation: &Rotation, pos: &Pos) -> Pos {
+    let mut out = [0; 3];
+    for (row, value) in out.iter_mut().enumerate() {
+        *value = (0 .. 3).map(|k| rotation[row][k] * pos[k]).sum();
+    }
+
+    out
+}
+
+/// Find the rotation and position of `scanner` that make at least 12 of
+/// its beacons land on those of `known`, given aligned as `aligned`,
+/// returning its aligned beacons and its position.
+pub fn align(
+    known: &Scanner,
+    aligned: &[Pos],
+    scanner: &Scanner,
+    rotations: &[Rotation],
+) -> Option<(Vec<Pos>, Pos)> {
+    let mut candidates = vec![];
+    for (a, near_a) in aligned.iter().zip(&known.neighbours) {
+        for (b, near_b) in scanner.beacons.iter().zip(&scanner.neighbours) {
+            if shared(near_a, near_b) >= OVERLAP - 1 {
+                candidates.push((a, b));
+            }
+        }
+    }
+    if candidates.len() < OVERLAP {
+        return None;
+    }
+
+    for rotation in rotations {
+        // Every candidate pair votes for the offset that would put its
+        // beacons on top of each other
+        let mut votes: HashMap<Pos, usize> = HashMap::new();
+        for (a, b) in &candidates {
+            let b = rotate(rotation, b);
+            let offset = [a[0] - b[0], a[1] - b[1], a[2] - b[2]];
+            let count = votes.entry(offset).or_default();
+            *count += 1;
+
+            if *count >= OVERLAP {
+                let beacons = scanner.beacons.iter()
+                    .map(|p| rotate(rotation, p))
+                    .map(|p| [p[0] + offset[0], p[1] + offset[1], p[2] + offset[2]])
+                    .collect();
+                return Some((beacons, offset));
+            }
+        }
+    }
+
+    None
+}
+
+/// Every beacon and scanner position, relative to scanner 0.
+#[derive(Debug)]
+pub struct Map {
+    /// Every distinct beacon.
+    pub beacons: HashSet<Pos>,
+    /// Where each scanner is, in report order.
+    pub scanners: Vec<Pos>,
+}
+
+/// Align every scanner with scanner 0, each through a chain of scanners
+/// that overlap.
+pub fn assemble(scanners: &[Scanner]) -> anyhow::Result<Map> {
+    let rotations = rotations();
+    let mut aligned: Vec<Option<(Vec<Pos>, Pos)>> = vec![None; scanners.len()];
+    let mut queue = VecDeque::new();
+
+    if scanners.is_empty() {
+        bail!("No scanners");
+    }
+    aligned[0] = Some((scanners[0].beacons.clone(), [0; 3]));
+    queue.push_back(0);
+
+    while let Some(i) = queue.pop_front() {
+        let known = aligned[i].as_ref().map(|(beacons, _)| beacons.clone())
+            .context("Queued scanners are aligned")?;
+
+        for j in 0 .. scanners.len() {
+            if aligned[j].is_some()
+                || shared(&scanners[i].distances, &scanners[j].distances) < SHARED_DISTANCES {
+                continue;
+            }
+
+            if let Some((beacons, pos)) = align(&scanners[i], &known, &scanners[j], &rotations) {
+                debug!(scanner = j, via = i, ?pos, "aligned");
+                aligned[j] = Some((beacons, pos));
+                queue.push_back(j);
+            }
+        }
+    }
+
+    let mut map = Map {beacons: HashSet::new(), scanners: vec![]};
+    for (i, scanner) in aligned.into_iter().enumerate() {
+        let (beacons, pos) = scanner
+            .with_context(|| format!("Scanner {} overlaps with no other", i))?;
+        map.beacons.extend(beacons);
+        map.scanners.push(pos);
+    }
+
+    Ok(map)
+}
+
+/// The scanners in the report, each headed by `--- scanner N ---`.
+pub fn parse(input: &str) -> anyhow::Result<Vec<Scanner>> {
+    let mut scanners = vec![];
+
+    for block in input.split("\n\n") {
+        let mut lines = block.lines();
+        match lines.next() {
+            Some(header) if header.starts_with("--- scanner") => {},
+            header => bail!("Invalid scanner header: {:?}", header),
+        }
+
+        let mut beacons = vec![];
+        for line in lines {
+            let mut pos = [0; 3];
+            let mut coords = line.split(',');
+            for value in pos.iter_mut() {
+                *value = coords.next()
+                    .and_then(|c| c.parse().ok())
+                    .with_context(|| format!("Invalid beacon: {}", line))?;
+            }
+            if coords.next().is_some() {
+                bail!("Invalid beacon: {}", line);
+            }
+            beacons.push(pos);
+        }
+
+        scanners.push(Scanner::new(beacons));
+    }
+
+    Ok(scanners)
+}
+
+/// Part 1: how many beacons there are.
+pub fn part1(map: &Map) -> usize {
+    map.beacons.len()
+}
+
+/// Part 2: the largest Manhattan distance between two scanners.
+pub fn part2(map: &Map) -> Num {
+    let mut max = Num::default();
+
+    for a in &map.scanners {
+        for b in &map.scanners {
+            let distance = (0 .. 3).map(|k| (a[k] - b[k]).abs()).sum::<i32>();
+            max = max.max(Num::of(distance));
+        }
+    }
+
+    max
+}
@@ -0,0 +1,17 @@
+//! Runs day 19, Beacon Scanner, on an input and prints the answers.
+
+fn main() -> anyhow::Result<()> {
+    let args = aoc::init();
+
+    let input = args.input(aoc::puzzle!())?;
+    let scanners = aoc::phase("parse", || day19_2021::parse(&input))?;
+
+    // Part 1
+    let map = aoc::phase("part1", || day19_2021::assemble(&scanners))?;
+    println!("{}", day19_2021::part1(&map));
+
+    // Part 2
+    println!("{}", aoc::phase("part2", || day19_2021::part2(&map)));
+
+    Ok(())
+}
@@ -0,0 +1,147 @@
+//! Day 20: Trench Map.
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{bail, ensure, Context};
+use tracing::debug;
+
+/// The image enhancement algorithm: whether the output pixel is lit for
+/// each of the 512 values of a 3x3 neighbourhood read as binary.
+#[derive(Debug, Clone)]
+pub struct Algorithm(Vec<bool>);
+
+fn pixel(c: char) -> anyhow::Result<bool> {
+    match c {
+        '#' => Ok(true),
+        '.' => Ok(false),
+        _ => bail!("Invalid pixel: {}", c),
+    }
+}
+
+impl FromStr for Algorithm {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let lit = s.trim().chars().map(pixel).collect::<anyhow::Result<Vec<_>>>()?;
+        ensure!(lit.len() == 512, "Expected 512 algorithm entries, got {}", lit.len());
+
+        Ok(Self(lit))
+    }
+}
+
+/// An image on the infinite plane: a rectangle of known pixels surrounded
+/// by a background where every pixel is the same.
+#[derive(Debug, Clone)]
+pub struct Image {
+    pixels: Vec<bool>,
+    rows: usize,
+    cols: usize,
+    background: bool,
+}
+
+impl FromStr for Image {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let cols = s.lines().next().map_or(0, str::len);
+        let mut pixels = vec![];
+        let mut rows = 0;
+
+        for line in s.lines() {
+            ensure!(line.len() == cols, "Invalid length for row {}", rows);
+            for c in line.chars() {
+                pixels.push(pixel(c)?);
+            }
+            rows += 1;
+        }
+
+        Ok(Self {pixels, rows, cols, background: false})
+    }
+}
+
+impl fmt::Display for Image {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.pixels.chunks(self.cols) {
+            for &lit in row {
+                write!(f, "{}", if lit { '#' } else { '.' })?;
+            }
+            writeln!(f)?;
+        }
+
+        fmt::Result::Ok(())
+    }
+}
+
+impl Image {
+    // The pixel at a position that may lie outside the known rectangle
+    fn get(&self, row: isize, col: isize) -> bool {
+        if row < 0 || col < 0 || row as usize >= self.rows || col as usize >= self.cols {
+            self.background
+        } else {
+            self.pixels[row as usize * self.cols + col as usize]
+        }
+    }
+
+    /// Apply the algorithm once. The known rectangle grows by one pixel on
+    /// every side, and the background goes with algorithm entry 0 when it
+    /// is dark and entry 511 when it is lit, so it flips every round when
+    /// entry 0 is lit and 511 is not.
+    pub fn enhance(&self, algorithm: &Algorithm) -> Self {
+        let (rows, cols) = (self.rows + 2, self.cols + 2);
+        let mut pixels = Vec::with_capacity(rows * cols);
+
+        for row in 0 .. rows as isize {
+            for col in 0 .. cols as isize {
+                let mut index = 0;
+                for r in row - 2 ..= row {
+                    for c in col - 2 ..= col {
+                        index = index << 1 | self.get(r, c) as usize;
+                    }
+                }
+                pixels.push(algorithm.0[index]);
+            }
+        }
+
+        let background = algorithm.0[if self.background { 511 } else { 0 }];
+
+        Self {pixels, rows, cols, background}
+    }
+
+    /// Apply the algorithm `n` times.
+    pub fn enhance_n(&self, algorithm: &Algorithm, n: usize) -> Self {
+        let mut image = self.clone();
+        for round in 0 .. n {
+            image = image.enhance(algorithm);
+            debug!(round, background = image.background, "enhanced");
+        }
+
+        image
+    }
+
+    /// How many pixels are lit, which is only finite while the background
+    /// is dark.
+    pub fn lit(&self) -> anyhow::Result<usize> {
+        ensure!(!self.background, "Infinitely many pixels are lit");
+
+        Ok(self.pixels.iter().filter(|&&lit| lit).count())
+    }
+}
+
+/// The algorithm and the input image, separated by a blank line.
+pub fn parse(input: &str) -> anyhow::Result<(Algorithm, Image)> {
+    let (algorithm, image) = input.split_once("\n\n")
+        .context("Expected the algorithm and the image separated by a blank line")?;
+
+    Ok((algorithm.parse()?, image.parse()?))
+}
+
+/// Part 1: lit pixels after enhancing twice.
+pub fn part1(algorithm: &Algorithm, image: &Image) -> anyhow::Result<usize> {
+    image.enhance_n(algorithm, 2).lit()
+}
+
+/// Part 2: lit pixels after enhancing 50 times.
+pub fn part2(algorithm: &Algorithm, image: &Image) -> anyhow::Result<usize> {
+    image.enhance_n(algorithm, 50).lit()
+}
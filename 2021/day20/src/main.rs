@@ -0,0 +1,16 @@
+//! Runs day 20, Trench Map, on an input and prints the answers.
+
+fn main() -> anyhow::Result<()> {
+    let args = aoc::init();
+
+    let input = args.input(aoc::puzzle!())?;
+    let (algorithm, image) = aoc::phase("parse", || day20_2021::parse(&input))?;
+
+    // Part 1
+    println!("{}", aoc::phase("part1", || day20_2021::part1(&algorithm, &image))?);
+
+    // Part 2
+    println!("{}", aoc::phase("part2", || day20_2021::part2(&algorithm, &image))?);
+
+    Ok(())
+}
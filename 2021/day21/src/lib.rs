@@ -0,0 +1,119 @@
+//! Day 21: Dirac Dice.
+
+use std::collections::HashMap;
+
+use anyhow::{ensure, Context};
+use aoc::num::Int;
+use tracing::debug;
+
+/// A score times a number of rolls.
+pub type Num = aoc::num::U32;
+
+/// How often each total of three rolls of the Dirac die comes up, from 3
+/// to 9, as the number of universes it splits into.
+const DIRAC_ROLLS: [(u32, u64); 7] = [(3, 1), (4, 3), (5, 6), (6, 7), (7, 6), (8, 3), (9, 1)];
+
+/// The state between two turns: where both pawns are, both scores and
+/// whose turn it is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Game {
+    /// The space each pawn is on, 1 to 10.
+    pub positions: [u32; 2],
+    /// Each player's score so far.
+    pub scores: [u32; 2],
+    /// The player to move next, 0 or 1.
+    pub turn: usize,
+}
+
+impl Game {
+    /// The game before the first turn, with the pawns on `positions`.
+    pub fn new(positions: [u32; 2]) -> Self {
+        Self {positions, scores: [0; 2], turn: 0}
+    }
+
+    /// The game after the player whose turn it is moves `steps` spaces.
+    pub fn play(mut self, steps: u32) -> Self {
+        let player = self.turn;
+        self.positions[player] = (self.positions[player] + steps - 1) % 10 + 1;
+        self.scores[player] += self.positions[player];
+        self.turn = 1 - player;
+
+        self
+    }
+}
+
+// Wins of each player over every universe that splits off `game`,
+// remembering the games already counted
+fn quantum(game: Game, target: u32, memo: &mut HashMap<Game, [u64; 2]>) -> [u64; 2] {
+    if let Some(&wins) = memo.get(&game) {
+        return wins;
+    }
+
+    let mut wins = [0; 2];
+    for (steps, universes) in DIRAC_ROLLS {
+        let next = game.play(steps);
+
+        if next.scores[game.turn] >= target {
+            wins[game.turn] += universes;
+        } else {
+            let [a, b] = quantum(next, target, memo);
+            wins[0] += a * universes;
+            wins[1] += b * universes;
+        }
+    }
+    memo.insert(game, wins);
+
+    wins
+}
+
+/// Wins of each player with the Dirac die, playing to 21.
+pub fn dirac_wins(start: [u32; 2]) -> [u64; 2] {
+    let mut memo = HashMap::new();
+    let wins = quantum(Game::new(start), 21, &mut memo);
+    debug!(states = memo.len(), ?wins, "quantum game played");
+
+    wins
+}
+
+/// The starting positions of both players.
+pub fn parse(input: &str) -> anyhow::Result<[u32; 2]> {
+    let mut start = [0; 2];
+    let mut lines = input.lines();
+
+    for (player, position) in start.iter_mut().enumerate() {
+        let line = lines.next()
+            .with_context(|| format!("No starting position for player {}", player + 1))?;
+        *position = line.rsplit_once(": ")
+            .and_then(|(_, pos)| pos.parse().ok())
+            .with_context(|| format!("Invalid starting position: {}", line))?;
+        ensure!((1 ..= 10).contains(position), "Invalid starting position: {}", line);
+    }
+
+    Ok(start)
+}
+
+/// Part 1: the losing score times the number of rolls, playing to 1000
+/// with a deterministic die that rolls 1 to 100 in turn.
+pub fn part1(start: [u32; 2]) -> Num {
+    let mut game = Game::new(start);
+    let mut rolls = 0;
+
+    loop {
+        // Three rolls starting at rolls + 1, wrapping after 100
+        let steps = (0 .. 3).map(|i| (rolls + i) % 100 + 1).sum();
+        let player = game.turn;
+        game = game.play(steps);
+        rolls += 3;
+
+        if game.scores[player] >= 1000 {
+            return Num::of(game.scores[1 - player]) * Num::of(rolls);
+        }
+    }
+}
+
+/// Part 2: how many universes the player who wins most often wins in.
+pub fn part2(start: [u32; 2]) -> u64 {
+    let [a, b] = dirac_wins(start);
+
+    a.max(b)
+}
@@ -0,0 +1,16 @@
+//! Runs day 21, Dirac Dice, on an input and prints the answers.
+
+fn main() -> anyhow::Result<()> {
+    let args = aoc::init();
+
+    let input = args.input(aoc::puzzle!())?;
+    let start = aoc::phase("parse", || day21_2021::parse(&input))?;
+
+    // Part 1
+    println!("{}", aoc::phase("part1", || day21_2021::part1(start)));
+
+    // Part 2
+    println!("{}", aoc::phase("part2", || day21_2021::part2(start)));
+
+    Ok(())
+}
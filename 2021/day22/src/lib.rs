@@ -0,0 +1,135 @@
+//! Day 22: Reactor Reboot.
+
+use std::str::FromStr;
+
+use anyhow::{bail, ensure, Context};
+use aoc::num::Int;
+use tracing::debug;
+
+/// A number of cubes.
+pub type Num = aoc::num::I64;
+
+/// A cuboid of cubes, with inclusive bounds on each axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cuboid {
+    /// The lowest `x`, `y` and `z` inside.
+    pub min: [i32; 3],
+    /// The highest `x`, `y` and `z` inside.
+    pub max: [i32; 3],
+}
+
+impl Cuboid {
+    /// The initialization procedure region, -50 to 50 on every axis.
+    pub const INIT: Self = Self {min: [-50; 3], max: [50; 3]};
+
+    /// The cubes in both cuboids, if there are any.
+    pub fn intersect(&self, other: &Self) -> Option<Self> {
+        let mut out = *self;
+
+        for k in 0 .. 3 {
+            out.min[k] = self.min[k].max(other.min[k]);
+            out.max[k] = self.max[k].min(other.max[k]);
+            if out.min[k] > out.max[k] {
+                return None;
+            }
+        }
+
+        Some(out)
+    }
+
+    /// How many cubes the cuboid holds.
+    pub fn volume(&self) -> Num {
+        (0 .. 3).map(|k| Num::of(self.max[k] - self.min[k] + 1)).product()
+    }
+}
+
+/// One reboot step, turning every cube of a cuboid on or off.
+#[derive(Debug, Clone, Copy)]
+pub struct Step {
+    /// Whether the step turns cubes on rather than off.
+    pub on: bool,
+    /// The cubes it turns on or off.
+    pub cuboid: Cuboid,
+}
+
+impl FromStr for Step {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let (on, ranges) = match s.split_once(' ') {
+            Some(("on", ranges)) => (true, ranges),
+            Some(("off", ranges)) => (false, ranges),
+            _ => bail!("Invalid step: {}", s),
+        };
+
+        let mut cuboid = Cuboid {min: [0; 3], max: [0; 3]};
+        let mut ranges = ranges.split(',');
+        for (k, axis) in ["x=", "y=", "z="].iter().enumerate() {
+            let (min, max) = ranges.next()
+                .and_then(|r| r.strip_prefix(axis))
+                .and_then(|r| r.split_once(".."))
+                .with_context(|| format!("Invalid step: {}", s))?;
+            cuboid.min[k] = min.parse().with_context(|| format!("Invalid step: {}", s))?;
+            cuboid.max[k] = max.parse().with_context(|| format!("Invalid step: {}", s))?;
+            ensure!(
+                cuboid.min[k] <= cuboid.max[k],
+                "Invalid step: {} ends before it starts in {}",
+                &axis[.. 1],
+                s,
+            );
+        }
+        if ranges.next().is_some() {
+            bail!("Invalid step: {}", s);
+        }
+
+        Ok(Self {on, cuboid})
+    }
+}
+
+/// How many cubes are on after the steps, by inclusion–exclusion.
+///
+/// Cuboids are kept with a sign. Every step cancels its overlap with each
+/// kept cuboid by adding the intersection with the opposite sign, and an
+/// "on" step then adds itself, so the signed volumes always sum to the
+/// cubes that are on.
+pub fn reboot<'a>(steps: impl IntoIterator<Item = &'a Step>) -> Num {
+    let mut cuboids: Vec<(Cuboid, bool)> = vec![];
+
+    for step in steps {
+        let overlaps: Vec<_> = cuboids.iter()
+            .filter_map(|(cuboid, positive)| {
+                cuboid.intersect(&step.cuboid).map(|i| (i, !positive))
+            })
+            .collect();
+        cuboids.extend(overlaps);
+
+        if step.on {
+            cuboids.push((step.cuboid, true));
+        }
+    }
+    debug!(cuboids = cuboids.len(), "reboot done");
+
+    cuboids.iter()
+        .map(|(cuboid, positive)| if *positive { cuboid.volume() } else { -cuboid.volume() })
+        .sum()
+}
+
+/// One step per line.
+pub fn parse(input: &str) -> anyhow::Result<Vec<Step>> {
+    input.lines().map(str::parse).collect()
+}
+
+/// Part 1: cubes on in the initialization region.
+pub fn part1(steps: &[Step]) -> Num {
+    let clipped: Vec<Step> = steps.iter()
+        .filter_map(|step| step.cuboid.intersect(&Cuboid::INIT)
+            .map(|cuboid| Step {cuboid, ..*step}))
+        .collect();
+
+    reboot(&clipped)
+}
+
+/// Part 2: cubes on anywhere.
+pub fn part2(steps: &[Step]) -> Num {
+    reboot(steps)
+}
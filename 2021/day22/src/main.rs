@@ -0,0 +1,16 @@
+//! Runs day 22, Reactor Reboot, on an input and prints the answers.
+
+fn main() -> anyhow::Result<()> {
+    let args = aoc::init();
+
+    let input = args.input(aoc::puzzle!())?;
+    let steps = aoc::phase("parse", || day22_2021::parse(&input))?;
+
+    // Part 1
+    println!("{}", aoc::phase("part1", || day22_2021::part1(&steps)));
+
+    // Part 2
+    println!("{}", aoc::phase("part2", || day22_2021::part2(&steps)));
+
+    Ok(())
+}
@@ -0,0 +1,222 @@
+//! Day 23: Amphipod.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+use anyhow::{bail, ensure, Context};
+use aoc::num::Int;
+use tracing::debug;
+
+/// An amount of energy.
+pub type Num = aoc::num::U32;
+
+const HALLWAY: usize = 11;
+const ROOMS: usize = 4;
+const MAX_DEPTH: usize = 4;
+
+/// Energy one step takes for amphipods A to D.
+const ENERGY: [u32; ROOMS] = [1, 10, 100, 1000];
+
+/// The amphipods the unfolded diagram adds between the two rows of each
+/// room, `#D#C#B#A#` over `#D#B#A#C#`.
+const UNFOLDED: [[u8; 2]; ROOMS] = [[4, 4], [3, 2], [2, 1], [1, 3]];
+
+// Hallway cell in front of a room, where amphipods never stop
+fn door(room: usize) -> usize {
+    2 + 2 * room
+}
+
+fn room_cell(room: usize, slot: usize) -> usize {
+    HALLWAY + room * MAX_DEPTH + slot
+}
+
+/// Every cell of the burrow, three bits each: 0 when empty and 1 to 4 for
+/// amphipods A to D. The hallway comes first, left to right, followed by
+/// the rooms from the left, each top to bottom.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct State(u128);
+
+impl State {
+    /// What is in `cell`.
+    pub fn get(self, cell: usize) -> u8 {
+        (self.0 >> (cell * 3) & 0b111) as u8
+    }
+
+    /// The state with `amphipod`, or 0 for nothing, in `cell`.
+    pub fn set(self, cell: usize, amphipod: u8) -> Self {
+        Self(self.0 & !(0b111 << (cell * 3)) | (amphipod as u128) << (cell * 3))
+    }
+
+    // Move whatever is in `from` to `to`
+    fn moved(self, from: usize, to: usize) -> Self {
+        self.set(to, self.get(from)).set(from, 0)
+    }
+}
+
+/// The burrow: how deep its rooms are and where the amphipods start.
+#[derive(Debug, Clone, Copy)]
+pub struct Burrow {
+    /// How many amphipods each room holds.
+    pub depth: usize,
+    /// Where they all start.
+    pub start: State,
+}
+
+impl Burrow {
+    /// The burrow with the two folded lines of the diagram inserted
+    /// between the first and second row of every room.
+    pub fn unfold(&self) -> anyhow::Result<Self> {
+        ensure!(self.depth == 2, "Only a 2 deep burrow can be unfolded");
+
+        let mut start = self.start;
+        for (room, [first, second]) in UNFOLDED.into_iter().enumerate() {
+            start = start.set(room_cell(room, 3), self.start.get(room_cell(room, 1)));
+            start = start.set(room_cell(room, 1), first);
+            start = start.set(room_cell(room, 2), second);
+        }
+
+        Ok(Self {depth: 4, start})
+    }
+
+    fn is_done(&self, state: State) -> bool {
+        (0 .. ROOMS).all(|room| {
+            (0 .. self.depth).all(|slot| state.get(room_cell(room, slot)) == room as u8 + 1)
+        })
+    }
+
+    // Whether only amphipods that belong in the room are in it
+    fn is_settled(&self, state: State, room: usize) -> bool {
+        (0 .. self.depth).all(|slot| {
+            let a = state.get(room_cell(room, slot));
+            a == 0 || a == room as u8 + 1
+        })
+    }
+
+    // Whether the hallway is empty between `from` and `to`, ignoring `from`
+    fn is_clear(state: State, from: usize, to: usize) -> bool {
+        let range = if from < to { from + 1 ..= to } else { to ..= from - 1 };
+        range.into_iter().all(|cell| state.get(cell) == 0)
+    }
+
+    /// Every legal move from `state` with the energy it takes: an
+    /// amphipod leaves its room for a free hallway cell, or goes from the
+    /// hallway into its own room once only its own kind is in there.
+    /// Moving straight between rooms is the same as both in a row.
+    pub fn moves(&self, state: State) -> Vec<(State, u32)> {
+        let mut moves = vec![];
+
+        // Into a room, from the hallway
+        for cell in 0 .. HALLWAY {
+            let amphipod = state.get(cell);
+            if amphipod == 0 {
+                continue;
+            }
+            let room = amphipod as usize - 1;
+            if !self.is_settled(state, room) || !Self::is_clear(state, cell, door(room)) {
+                continue;
+            }
+
+            // The deepest free slot
+            let Some(slot) = (0 .. self.depth).rev()
+                .find(|&slot| state.get(room_cell(room, slot)) == 0) else {
+                continue;
+            };
+            let steps = cell.abs_diff(door(room)) + slot + 1;
+            moves.push((state.moved(cell, room_cell(room, slot)), steps as u32 * ENERGY[room]));
+        }
+
+        // Out of a room, into the hallway
+        for room in 0 .. ROOMS {
+            if self.is_settled(state, room) {
+                continue;
+            }
+            let Some(slot) = (0 .. self.depth)
+                .find(|&slot| state.get(room_cell(room, slot)) != 0) else {
+                continue;
+            };
+            let from = room_cell(room, slot);
+            let energy = ENERGY[state.get(from) as usize - 1];
+
+            for cell in (0 .. HALLWAY).filter(|&cell| !(2 ..= 8).contains(&cell) || cell % 2 == 1) {
+                if Self::is_clear(state, door(room), cell) {
+                    let steps = cell.abs_diff(door(room)) + slot + 1;
+                    moves.push((state.moved(from, cell), steps as u32 * energy));
+                }
+            }
+        }
+
+        moves
+    }
+
+    /// The least energy to get every amphipod into its own room, found
+    /// with Dijkstra's algorithm over burrow states.
+    pub fn organize(&self) -> Option<Num> {
+        let mut best = HashMap::new();
+        let mut queue = BinaryHeap::new();
+
+        best.insert(self.start, 0);
+        queue.push(Reverse((0, self.start)));
+
+        while let Some(Reverse((energy, state))) = queue.pop() {
+            if self.is_done(state) {
+                debug!(states = best.len(), energy, "organized");
+                return Some(Num::of(energy));
+            }
+            if best.get(&state).is_some_and(|&e| e < energy) {
+                continue;
+            }
+
+            for (next, cost) in self.moves(state) {
+                let energy = energy + cost;
+                if best.get(&next).is_none_or(|&e| energy < e) {
+                    best.insert(next, energy);
+                    queue.push(Reverse((energy, next)));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// The diagram of the burrow, with rooms 2 or 4 deep.
+pub fn parse(input: &str) -> anyhow::Result<Burrow> {
+    let lines: Vec<&str> = input.lines().collect();
+    ensure!(lines.len() >= 5, "Invalid diagram: too few lines");
+
+    let amphipod = |c: char| match c {
+        '.' => Ok(0),
+        'A' ..= 'D' => Ok(c as u8 - b'A' + 1),
+        _ => bail!("Invalid cell: {}", c),
+    };
+
+    let mut start = State(0);
+    let hallway = lines[1].get(1 .. 1 + HALLWAY)
+        .with_context(|| format!("Invalid hallway: {}", lines[1]))?;
+    for (cell, c) in hallway.chars().enumerate() {
+        start = start.set(cell, amphipod(c)?);
+    }
+
+    // Every line between the hallway and the bottom wall is a row of rooms
+    let rows = &lines[2 .. lines.len() - 1];
+    ensure!(rows.len() <= MAX_DEPTH, "Rooms deeper than {}", MAX_DEPTH);
+    for (slot, line) in rows.iter().enumerate() {
+        for room in 0 .. ROOMS {
+            let c = line.chars().nth(3 + 2 * room)
+                .with_context(|| format!("Invalid room row: {}", line))?;
+            start = start.set(room_cell(room, slot), amphipod(c)?);
+        }
+    }
+
+    Ok(Burrow {depth: rows.len(), start})
+}
+
+/// Part 1: the least energy to organize the burrow as drawn.
+pub fn part1(burrow: &Burrow) -> anyhow::Result<Num> {
+    burrow.organize().context("The amphipods can not be organized")
+}
+
+/// Part 2: the least energy to organize the unfolded burrow.
+pub fn part2(burrow: &Burrow) -> anyhow::Result<Num> {
+    burrow.unfold()?.organize().context("The amphipods can not be organized")
+}
@@ -0,0 +1,16 @@
+//! Runs day 23, Amphipod, on an input and prints the answers.
+
+fn main() -> anyhow::Result<()> {
+    let args = aoc::init();
+
+    let input = args.input(aoc::puzzle!())?;
+    let burrow = aoc::phase("parse", || day23_2021::parse(&input))?;
+
+    // Part 1
+    println!("{}", aoc::phase("part1", || day23_2021::part1(&burrow))?);
+
+    // Part 2
+    println!("{}", aoc::phase("part2", || day23_2021::part2(&burrow))?);
+
+    Ok(())
+}
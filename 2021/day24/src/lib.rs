@@ -0,0 +1,338 @@
+//! Day 24: Arithmetic Logic Unit.
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{bail, ensure, Context};
+use aoc::num::Int;
+use tracing::debug;
+
+/// A model number.
+pub type Num = aoc::num::U64;
+
+/// Digits in a model number, one per `inp` of MONAD.
+pub const DIGITS: usize = 14;
+
+/// One of the ALU's registers, `w`, `x`, `y` and `z` in order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Reg(usize);
+
+impl FromStr for Reg {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "w" => Ok(Self(0)),
+            "x" => Ok(Self(1)),
+            "y" => Ok(Self(2)),
+            "z" => Ok(Self(3)),
+            _ => bail!("Invalid register: {}", s),
+        }
+    }
+}
+
+/// The second operand of an instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand {
+    /// The value of a register.
+    Reg(Reg),
+    /// A number written in the instruction.
+    Value(i64),
+}
+
+impl FromStr for Operand {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s.parse() {
+            Ok(value) => Ok(Self::Value(value)),
+            Err(_) => s.parse().map(Self::Reg),
+        }
+    }
+}
+
+/// An ALU instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    /// Read the next input digit into the register.
+    Inp(Reg),
+    /// Add the operand to the register.
+    Add(Reg, Operand),
+    /// Multiply the register by the operand.
+    Mul(Reg, Operand),
+    /// Divide the register by the operand, rounding toward zero.
+    Div(Reg, Operand),
+    /// Replace the register with its remainder after dividing by the
+    /// operand.
+    Mod(Reg, Operand),
+    /// Set the register to 1 if it equals the operand, else 0.
+    Eql(Reg, Operand),
+}
+
+impl FromStr for Instruction {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let words: Vec<&str> = s.split_whitespace().collect();
+
+        Ok(match words.as_slice() {
+            ["inp", a] => Self::Inp(a.parse()?),
+            ["add", a, b] => Self::Add(a.parse()?, b.parse()?),
+            ["mul", a, b] => Self::Mul(a.parse()?, b.parse()?),
+            ["div", a, b] => Self::Div(a.parse()?, b.parse()?),
+            ["mod", a, b] => Self::Mod(a.parse()?, b.parse()?),
+            ["eql", a, b] => Self::Eql(a.parse()?, b.parse()?),
+            _ => bail!("Invalid instruction: {}", s),
+        })
+    }
+}
+
+impl fmt::Display for Reg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", ['w', 'x', 'y', 'z'][self.0])
+    }
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Reg(reg) => write!(f, "{}", reg),
+            Self::Value(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Inp(a) => write!(f, "inp {}", a),
+            Self::Add(a, b) => write!(f, "add {} {}", a, b),
+            Self::Mul(a, b) => write!(f, "mul {} {}", a, b),
+            Self::Div(a, b) => write!(f, "div {} {}", a, b),
+            Self::Mod(a, b) => write!(f, "mod {} {}", a, b),
+            Self::Eql(a, b) => write!(f, "eql {} {}", a, b),
+        }
+    }
+}
+
+/// The ALU's registers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Alu {
+    /// `w`, `x`, `y` and `z` in order.
+    pub regs: [i64; 4],
+}
+
+impl Alu {
+    fn value(&self, operand: Operand) -> i64 {
+        match operand {
+            Operand::Reg(reg) => self.regs[reg.0],
+            Operand::Value(value) => value,
+        }
+    }
+
+    /// Run `program` from the current registers, reading `input` in order.
+    /// Dividing by zero and taking the modulo of a negative number or by
+    /// a non-positive one stop the ALU with an error.
+    pub fn run(&mut self, program: &[Instruction], input: &[i64]) -> anyhow::Result<()> {
+        let mut input = input.iter();
+
+        for (line, instruction) in program.iter().enumerate() {
+            let (a, result) = match *instruction {
+                Instruction::Inp(a) => (a, *input.next()
+                    .with_context(|| format!("Line {}: out of input", line + 1))?),
+                Instruction::Add(a, b) => (a, self.regs[a.0] + self.value(b)),
+                Instruction::Mul(a, b) => (a, self.regs[a.0] * self.value(b)),
+                Instruction::Div(a, b) => {
+                    let b = self.value(b);
+                    ensure!(b != 0, "Line {}: {} divides by zero", line + 1, instruction);
+                    (a, self.regs[a.0] / b)
+                },
+                Instruction::Mod(a, b) => {
+                    let (value, b) = (self.regs[a.0], self.value(b));
+                    ensure!(
+                        value >= 0 && b > 0,
+                        "Line {}: {} is {} mod {}",
+                        line + 1,
+                        instruction,
+                        value,
+                        b,
+                    );
+                    (a, value % b)
+                },
+                Instruction::Eql(a, b) => (a, (self.regs[a.0] == self.value(b)) as i64),
+            };
+            self.regs[a.0] = result;
+        }
+
+        Ok(())
+    }
+}
+
+/// Whether MONAD accepts the model number with these digits, that is
+/// whether it leaves 0 in `z`.
+pub fn accepts(program: &[Instruction], digits: &[i64]) -> anyhow::Result<bool> {
+    let mut alu = Alu::default();
+    alu.run(program, digits)?;
+
+    Ok(alu.regs[3] == 0)
+}
+
+/// The parameters of one of MONAD's 14 blocks, which are the same but for
+/// three values.
+///
+/// Each block reads a digit and treats `z` as a stack of base 26 digits.
+/// With `div z 1` it pushes the digit plus `push`. With `div z 26` it pops
+/// the top, and pushes nothing back only when the digit is what was
+/// popped plus `check`. `z` ends at 0 when every pop avoids its push.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Block {
+    /// Whether the block divides `z` by 26 rather than 1.
+    pub pops: bool,
+    /// The number added to `x` before comparing it with the digit.
+    pub check: i64,
+    /// The number added to the digit before pushing it.
+    pub push: i64,
+}
+
+const BLOCK: [&str; 18] = [
+    "inp w", "mul x 0", "add x z", "mod x 26", "div z _", "add x _",
+    "eql x w", "eql x 0", "mul y 0", "add y 25", "mul y x", "add y 1",
+    "mul z y", "mul y 0", "add y w", "add y _", "mul y x", "add z y",
+];
+
+/// Split MONAD into its blocks, checking every instruction against the
+/// shape they all share.
+pub fn blocks(program: &[Instruction]) -> anyhow::Result<Vec<Block>> {
+    ensure!(
+        program.len() == DIGITS * BLOCK.len(),
+        "Expected {} instructions, got {}",
+        DIGITS * BLOCK.len(),
+        program.len(),
+    );
+
+    let mut blocks = vec![];
+    for (n, chunk) in program.chunks(BLOCK.len()).enumerate() {
+        let mut values: Vec<i64> = vec![];
+
+        for (i, (instruction, expected)) in chunk.iter().zip(BLOCK).enumerate() {
+            let text = instruction.to_string();
+            let matches = match expected.strip_suffix('_') {
+                Some(prefix) => match text.strip_prefix(prefix).and_then(|v| v.parse().ok()) {
+                    Some(value) => {
+                        values.push(value);
+                        true
+                    },
+                    None => false,
+                },
+                None => text == expected,
+            };
+            ensure!(
+                matches,
+                "Line {} is {}, not like {} in every MONAD block",
+                n * BLOCK.len() + i + 1,
+                text,
+                expected,
+            );
+        }
+
+        let pops = match values[0] {
+            1 => false,
+            26 => true,
+            div => bail!("Block {} divides z by {}, not 1 or 26", n + 1, div),
+        };
+        blocks.push(Block {pops, check: values[1], push: values[2]});
+    }
+
+    Ok(blocks)
+}
+
+/// The pairs of digits MONAD ties together, as `(i, j, offset)` where
+/// digit `j` has to be digit `i` plus `offset`.
+///
+/// `examples/hand-built.txt` is a MONAD written to have these, worked out
+/// by hand from its blocks:
+///
+/// ```
+/// use aoc::num::Int;
+///
+/// let program = day24_2021::parse(include_str!("../examples/hand-built.txt")).unwrap();
+/// let blocks = day24_2021::blocks(&program).unwrap();
+///
+/// // Every push checks 10 or more, which no digit can match, so it always
+/// // pushes. Each pop's digit must be the popped one plus its check.
+/// // Digit 1 pushes d1 + 2, digit 6 pops it checking 2:      d6 = d1 + 4
+/// // Digit 2 pushes d2 + 5, digit 3 pops it checking -8:     d3 = d2 - 3
+/// // Digit 4 pushes d4 + 0, digit 5 pops it checking  0:     d5 = d4
+/// // Digit 7 pushes d7 + 14, digit 14 pops it checking -9:   d14 = d7 + 5
+/// // Digit 8 pushes d8 + 1, digit 11 pops it checking 7:     d11 = d8 + 8
+/// // Digit 9 pushes d9 + 7, digit 10 pops it checking -15:   d10 = d9 - 8
+/// // Digit 12 pushes d12 + 3, digit 13 pops it checking -5:  d13 = d12 - 2
+/// assert_eq!(day24_2021::constraints(&blocks).unwrap(), [
+///     (1, 2, -3), (3, 4, 0), (0, 5, 4), (8, 9, -8), (7, 10, 8), (11, 12, -2), (6, 13, 5),
+/// ]);
+///
+/// // Largest:  d1..d14 = 5 9 6 9 9 9 4 1 9 1 9 9 7 9
+/// // Smallest: d1..d14 = 1 4 1 1 1 5 1 1 9 1 9 3 1 6
+/// assert_eq!(day24_2021::part1(&program).unwrap().get(), 59699941919979);
+/// assert_eq!(day24_2021::part2(&program).unwrap().get(), 14111511919316);
+/// ```
+pub fn constraints(blocks: &[Block]) -> anyhow::Result<Vec<(usize, usize, i64)>> {
+    let mut stack: Vec<usize> = vec![];
+    let mut constraints = vec![];
+
+    for (j, block) in blocks.iter().enumerate() {
+        if block.pops {
+            let i = stack.pop()
+                .with_context(|| format!("Block {} pops an empty stack", j + 1))?;
+            let offset = blocks[i].push + block.check;
+            debug!(i, j, offset, "digits tied");
+            constraints.push((i, j, offset));
+        } else {
+            stack.push(j);
+        }
+    }
+    ensure!(stack.is_empty(), "{} pushes are never popped", stack.len());
+
+    Ok(constraints)
+}
+
+/// The largest or smallest model number MONAD accepts, worked out from the
+/// digit constraints and then run through the ALU to make sure.
+pub fn model_number(program: &[Instruction], largest: bool) -> anyhow::Result<Num> {
+    let mut digits = [0; DIGITS];
+
+    for (i, j, offset) in constraints(&blocks(program)?)? {
+        // The best digit i whose partner is still a digit
+        let mut candidates = (1 ..= 9).filter(|d| (1 ..= 9).contains(&(d + offset)));
+        let d = if largest { candidates.next_back() } else { candidates.next() }
+            .with_context(|| format!("Digits {} and {} can not differ by {}", i + 1, j + 1, offset))?;
+        digits[i] = d;
+        digits[j] = d + offset;
+    }
+
+    ensure!(
+        accepts(program, &digits)?,
+        "MONAD rejects {:?}, the program does not work as analysed",
+        digits,
+    );
+
+    Ok(digits.iter().fold(Num::default(), |n, &d| n * 10 + Num::of(d)))
+}
+
+/// One instruction per line.
+pub fn parse(input: &str) -> anyhow::Result<Vec<Instruction>> {
+    input.lines()
+        .enumerate()
+        .map(|(i, line)| line.parse().with_context(|| format!("Line {}", i + 1)))
+        .collect()
+}
+
+/// Part 1: the largest model number MONAD accepts.
+pub fn part1(program: &[Instruction]) -> anyhow::Result<Num> {
+    model_number(program, true)
+}
+
+/// Part 2: the smallest model number MONAD accepts.
+pub fn part2(program: &[Instruction]) -> anyhow::Result<Num> {
+    model_number(program, false)
+}
@@ -0,0 +1,16 @@
+//! Runs day 24, Arithmetic Logic Unit, on an input and prints the answers.
+
+fn main() -> anyhow::Result<()> {
+    let args = aoc::init();
+
+    let input = args.input(aoc::puzzle!())?;
+    let program = aoc::phase("parse", || day24_2021::parse(&input))?;
+
+    // Part 1
+    println!("{}", aoc::phase("part1", || day24_2021::part1(&program))?);
+
+    // Part 2
+    println!("{}", aoc::phase("part2", || day24_2021::part2(&program))?);
+
+    Ok(())
+}
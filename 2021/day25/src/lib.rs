@@ -0,0 +1,121 @@
+//! Day 25: Sea Cucumber.
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{bail, ensure};
+use tracing::trace;
+
+/// What is in one location of the sea floor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cell {
+    /// `.`
+    Empty,
+    /// `>`, a sea cucumber of the east-facing herd.
+    East,
+    /// `v`, a sea cucumber of the south-facing herd.
+    South,
+}
+
+/// Both herds of sea cucumbers on a sea floor that wraps around at the
+/// edges.
+#[derive(Debug, Clone)]
+pub struct Herds {
+    cells: Vec<Cell>,
+    rows: usize,
+    cols: usize,
+}
+
+impl FromStr for Herds {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let cols = s.lines().next().map_or(0, str::len);
+        let mut cells = vec![];
+        let mut rows = 0;
+
+        for line in s.lines() {
+            ensure!(line.len() == cols, "Invalid length for row {}", rows);
+            for c in line.chars() {
+                cells.push(match c {
+                    '.' => Cell::Empty,
+                    '>' => Cell::East,
+                    'v' => Cell::South,
+                    _ => bail!("Invalid cell: {}", c),
+                });
+            }
+            rows += 1;
+        }
+
+        Ok(Self {cells, rows, cols})
+    }
+}
+
+impl fmt::Display for Herds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.cells.chunks(self.cols) {
+            for cell in row {
+                let c = match cell {
+                    Cell::Empty => '.',
+                    Cell::East => '>',
+                    Cell::South => 'v',
+                };
+                write!(f, "{}", c)?;
+            }
+            writeln!(f)?;
+        }
+
+        fmt::Result::Ok(())
+    }
+}
+
+impl Herds {
+    // Move every sea cucumber of one herd whose next location is empty,
+    // all at once, returning how many moved
+    fn phase(&mut self, herd: Cell) -> usize {
+        let before = self.cells.clone();
+        let mut moved = 0;
+
+        for row in 0 .. self.rows {
+            for col in 0 .. self.cols {
+                if before[row * self.cols + col] != herd {
+                    continue;
+                }
+
+                let next = match herd {
+                    Cell::East => row * self.cols + (col + 1) % self.cols,
+                    _ => (row + 1) % self.rows * self.cols + col,
+                };
+                if before[next] == Cell::Empty {
+                    self.cells[row * self.cols + col] = Cell::Empty;
+                    self.cells[next] = herd;
+                    moved += 1;
+                }
+            }
+        }
+
+        moved
+    }
+
+    /// Move the east-facing herd, then the south-facing one, returning how
+    /// many sea cucumbers moved in all.
+    pub fn step(&mut self) -> usize {
+        let east = self.phase(Cell::East);
+        let south = self.phase(Cell::South);
+        trace!(east, south, "step");
+
+        east + south
+    }
+}
+
+/// Part 1: the first step on which no sea cucumber moves.
+pub fn part1(herds: &Herds) -> usize {
+    let mut herds = herds.clone();
+    let mut steps = 1;
+
+    while herds.step() > 0 {
+        steps += 1;
+    }
+
+    steps
+}
@@ -0,0 +1,15 @@
+//! Runs day 25, Sea Cucumber, on an input and prints the answers.
+
+use std::str::FromStr;
+
+fn main() -> anyhow::Result<()> {
+    let args = aoc::init();
+
+    let input = args.input(aoc::puzzle!())?;
+    let herds = aoc::phase("parse", || day25_2021::Herds::from_str(&input))?;
+
+    // Part 1, the last day has no part 2
+    println!("{}", aoc::phase("part1", || day25_2021::part1(&herds)));
+
+    Ok(())
+}
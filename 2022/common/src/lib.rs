@@ -0,0 +1,5 @@
+//! Helpers shared by the 2022 days, depended on as
+//! `common = { package = "common2022", path = "../common" }`.
+//!
+//! Nothing is shared yet: parsing and models move here once a second day
+//! needs them.
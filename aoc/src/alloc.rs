@@ -0,0 +1,106 @@
+//! Allocation counting for the parse, part 1 and part 2 phases, built in
+//! with the `alloc` feature.
+//!
+//! The feature replaces the global allocator with one that counts
+//! allocations and tracks live heap memory, and adds an `--alloc` option.
+//! With it every phase prints one line to stderr in the form
+//! `alloc <name>: <count> allocs, <bytes> bytes, <peak> peak`, where bytes
+//! is the total allocated during the phase and peak the most heap memory
+//! that was live at once.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+static REPORT: AtomicBool = AtomicBool::new(false);
+
+static ALLOCS: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+struct Counting;
+
+fn allocated(size: usize) {
+    ALLOCS.fetch_add(1, Ordering::Relaxed);
+    BYTES.fetch_add(size, Ordering::Relaxed);
+    let live = LIVE.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(live, Ordering::Relaxed);
+}
+
+fn freed(size: usize) {
+    LIVE.fetch_sub(size, Ordering::Relaxed);
+}
+
+// SAFETY: every call is passed on to the system allocator unchanged,
+// the counters only look at the sizes
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        freed(layout.size());
+    }
+
+    // A reallocation counts as allocating the new size and freeing
+    // the old one, whether or not the block moved
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            allocated(new_size);
+            freed(layout.size());
+        }
+        new
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+pub(crate) fn report_allocs(enabled: bool) {
+    REPORT.store(enabled, Ordering::Relaxed);
+}
+
+/// The counters at the start of a phase.
+pub(crate) struct Mark {
+    allocs: usize,
+    bytes: usize,
+}
+
+impl Mark {
+    /// Start counting a phase, or `None` when allocations are not reported.
+    pub(crate) fn start() -> Option<Self> {
+        if !REPORT.load(Ordering::Relaxed) {
+            return None;
+        }
+
+        PEAK.store(LIVE.load(Ordering::Relaxed), Ordering::Relaxed);
+        Some(Self {
+            allocs: ALLOCS.load(Ordering::Relaxed),
+            bytes: BYTES.load(Ordering::Relaxed),
+        })
+    }
+
+    pub(crate) fn report(self, name: &str) {
+        eprintln!(
+            "alloc {}: {} allocs, {} bytes, {} peak",
+            name,
+            ALLOCS.load(Ordering::Relaxed) - self.allocs,
+            BYTES.load(Ordering::Relaxed) - self.bytes,
+            PEAK.load(Ordering::Relaxed),
+        );
+    }
+}
@@ -0,0 +1,52 @@
+use anyhow::Context;
+
+use crate::day::{self, Day};
+use crate::report::{millis, Table};
+
+pub fn all(year: u32) -> anyhow::Result<()> {
+    let days = Day::all(year)?;
+    if days.is_empty() {
+        println!("No days for {} yet", year);
+        return Ok(());
+    }
+    day::build(&days)?;
+
+    let tmp = std::env::temp_dir().join(format!("aoc-all-{}", std::process::id()));
+    std::fs::create_dir_all(&tmp)
+        .with_context(|| format!("Could not create {}", tmp.display()))?;
+
+    let mut table = Table::new(&["day", "part 1", "part 2", "total (ms)", "error"]);
+    let mut total = std::time::Duration::ZERO;
+
+    for day in &days {
+        let Ok(data) = day.puzzle.read() else {
+            table.push(vec![
+                day.name.clone(), String::new(), String::new(),
+                String::new(), "no input".into(),
+            ]);
+            continue;
+        };
+
+        let input = tmp.join(format!("{}.txt", day.name));
+        std::fs::write(&input, data)
+            .with_context(|| format!("Could not write {}", input.display()))?;
+        let run = day.run(&input, &[])?;
+        total += run.wall;
+
+        table.push(vec![
+            day.name.clone(),
+            run.part1.unwrap_or_default(),
+            run.part2.unwrap_or_default(),
+            millis(Some(run.wall)),
+            run.error.unwrap_or_default(),
+        ]);
+    }
+
+    std::fs::remove_dir_all(&tmp)
+        .with_context(|| format!("Could not remove {}", tmp.display()))?;
+
+    print!("{}", table.to_markdown());
+    eprintln!("{}: {} days in {} ms", year, days.len(), millis(Some(total)));
+
+    Ok(())
+}
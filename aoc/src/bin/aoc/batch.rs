@@ -0,0 +1,115 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+
+use crate::day::{Day, Run};
+use crate::report::{millis, Table};
+
+#[derive(Debug, clap::Args)]
+pub struct BatchArgs {
+    /// Day to run
+    day: u32,
+
+    /// Directory holding one puzzle input per file
+    dir: PathBuf,
+
+    /// Variants to run on every input (comma separated); inputs where
+    /// their answers differ are flagged
+    #[arg(long = "variant", value_delimiter = ',')]
+    variants: Vec<String>,
+
+    /// Write the report as CSV to this file
+    #[arg(long)]
+    csv: Option<PathBuf>,
+
+    /// Write the report as a Markdown table to this file
+    #[arg(long)]
+    markdown: Option<PathBuf>,
+}
+
+pub fn batch(year: u32, args: BatchArgs) -> anyhow::Result<()> {
+    let day = Day::new(year, args.day)?;
+    day.build()?;
+
+    let mut inputs = std::fs::read_dir(&args.dir)
+        .with_context(|| format!("Could not read {}", args.dir.display()))?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    inputs.retain(|path| path.is_file());
+    inputs.sort();
+
+    let variants: Vec<Option<&str>> = if args.variants.is_empty() {
+        vec![None]
+    } else {
+        args.variants.iter().map(|v| Some(v.as_str())).collect()
+    };
+
+    let mut table = Table::new(&[
+        "input", "variant", "part 1", "part 2",
+        "parse (ms)", "part 1 (ms)", "part 2 (ms)", "total (ms)",
+        "agree", "error",
+    ]);
+    let mut disagreements = 0;
+
+    for input in &inputs {
+        let mut runs: Vec<(Option<&str>, Run)> = vec![];
+
+        for &variant in &variants {
+            let extra: Vec<&str> = variant.iter()
+                .flat_map(|v| ["--variant", v])
+                .collect();
+            runs.push((variant, day.run(input, &extra)?));
+        }
+
+        // A variant that fails where another answers disagrees with it
+        let answers: Vec<_> = runs.iter()
+            .map(|(_, run)| (run.answers(), run.error.is_some()))
+            .collect();
+        let agree = answers.windows(2).all(|pair| pair[0] == pair[1]);
+        if !agree {
+            disagreements += 1;
+        }
+        let agree = match (variants.len(), agree) {
+            (1, _) => "-",
+            (_, true) => "yes",
+            (_, false) => "NO",
+        };
+
+        let name = input.file_name().unwrap_or_default().to_string_lossy();
+        for (variant, run) in runs {
+            table.push(vec![
+                name.to_string(),
+                variant.unwrap_or("default").into(),
+                run.part1.clone().unwrap_or_default(),
+                run.part2.clone().unwrap_or_default(),
+                millis(run.time("parse")),
+                millis(run.time("part1")),
+                millis(run.time("part2")),
+                millis(Some(run.wall)),
+                agree.into(),
+                run.error.unwrap_or_default(),
+            ]);
+        }
+    }
+
+    if let Some(path) = &args.csv {
+        std::fs::write(path, table.to_csv())
+            .with_context(|| format!("Could not write {}", path.display()))?;
+    }
+    if let Some(path) = &args.markdown {
+        std::fs::write(path, table.to_markdown())
+            .with_context(|| format!("Could not write {}", path.display()))?;
+    }
+    if args.csv.is_none() && args.markdown.is_none() {
+        print!("{}", table.to_markdown());
+    }
+
+    eprintln!(
+        "{}: {} inputs, {} with disagreeing variants",
+        day.name,
+        inputs.len(),
+        disagreements,
+    );
+
+    Ok(())
+}
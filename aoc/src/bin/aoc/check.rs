@@ -0,0 +1,134 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context};
+
+use crate::day::{self, Day};
+use crate::fixture::Answers;
+use crate::snapshot;
+
+#[derive(Debug, clap::Args)]
+pub struct CheckArgs {
+    /// Days to check, all of them when empty
+    days: Vec<u32>,
+}
+
+// The same input as saved by a Windows editor: with a byte order mark,
+// CRLF line endings and trailing blank lines
+fn windows_copy(data: &str) -> String {
+    format!("\u{feff}{}\r\n\r\n", data.trim_end_matches('\n').replace('\n', "\r\n"))
+}
+
+struct Checker {
+    tmp: PathBuf,
+    checks: usize,
+    failures: usize,
+}
+
+impl Checker {
+    fn report(&mut self, day: &Day, name: &str, result: anyhow::Result<()>) {
+        self.checks += 1;
+
+        match result {
+            Ok(()) => println!("ok   {} {}", day.name, name),
+            Err(e) => {
+                self.failures += 1;
+                println!("FAIL {} {}: {:#}", day.name, name, e);
+            },
+        }
+    }
+
+    // Run the day on an input, compare with the expected answers if there
+    // are any, and check a Windows copy of the input gives the same answers
+    fn input(
+        &mut self,
+        day: &Day,
+        name: &str,
+        input: &Path,
+        answers: Option<&Answers>,
+    ) -> anyhow::Result<()> {
+        let expected = day.run(input, &[])?;
+        if let Some(e) = &expected.error {
+            self.report(day, name, Err(anyhow::anyhow!(e.clone())));
+            return Ok(());
+        }
+
+        if let Some(answers) = answers {
+            let mut result = Ok(());
+
+            for (part, want, got) in [
+                (1, &answers.part1, &expected.part1),
+                (2, &answers.part2, &expected.part2),
+            ] {
+                if want.is_some() && want != got {
+                    result = Err(anyhow::anyhow!(
+                        "part {}: expected {}, got {}",
+                        part,
+                        want.as_deref().unwrap_or_default(),
+                        got.as_deref().unwrap_or("nothing"),
+                    ));
+                    break;
+                }
+            }
+
+            self.report(day, name, result);
+        }
+
+        let crlf = self.tmp.join(format!("{}-crlf-{}", day.name, name.replace('/', "-")));
+        std::fs::write(&crlf, windows_copy(&aoc::input::read(input)?))
+            .with_context(|| format!("Could not write {}", crlf.display()))?;
+        let actual = day.run(&crlf, &[])?;
+
+        let result = match actual.error {
+            Some(e) => Err(anyhow::anyhow!(e)),
+            None if actual.answers() != expected.answers() => Err(anyhow::anyhow!(
+                "expected {:?}, got {:?}",
+                expected.answers(),
+                actual.answers(),
+            )),
+            None => Ok(()),
+        };
+        self.report(day, &format!("{} (crlf)", name), result);
+
+        Ok(())
+    }
+}
+
+pub fn check(year: u32, args: CheckArgs) -> anyhow::Result<()> {
+    let days = Day::select(year, &args.days)?;
+    day::build(&days)?;
+
+    let tmp = std::env::temp_dir().join(format!("aoc-check-{}", std::process::id()));
+    std::fs::create_dir_all(&tmp)
+        .with_context(|| format!("Could not create {}", tmp.display()))?;
+    let mut checker = Checker {tmp, checks: 0, failures: 0};
+
+    for day in &days {
+        for example in day.examples()? {
+            checker.input(day, &example.name, &example.input, example.answers.as_ref())?;
+        }
+
+        for snapshot in snapshot::snapshots(day)? {
+            let result = snapshot.run(day)
+                .and_then(|actual| snapshot.compare(&actual));
+            checker.report(day, &snapshot.name, result);
+        }
+
+        // The real input is only there when it has been added to the store
+        if let Ok(data) = day.puzzle.read() {
+            let input = checker.tmp.join(format!("{}-input.txt", day.name));
+            std::fs::write(&input, data)
+                .with_context(|| format!("Could not write {}", input.display()))?;
+            checker.input(day, "input.txt", &input, None)?;
+        }
+    }
+
+    std::fs::remove_dir_all(&checker.tmp)
+        .with_context(|| format!("Could not remove {}", checker.tmp.display()))?;
+
+    println!("{} checks, {} failed", checker.checks, checker.failures);
+    if checker.failures > 0 {
+        bail!("{} checks failed", checker.failures);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,250 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+use aoc::Puzzle;
+use anyhow::{bail, ensure, Context};
+
+use crate::fixture::{self, Example};
+
+/// Root of the workspace, one level above this crate.
+pub fn root() -> &'static Path {
+    aoc::input::root()
+}
+
+fn target_dir() -> PathBuf {
+    std::env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| root().join("target"))
+}
+
+/// Every year with a directory in the workspace, in order.
+pub fn years() -> anyhow::Result<Vec<u32>> {
+    let mut years = vec![];
+
+    for entry in std::fs::read_dir(root())
+        .with_context(|| format!("Could not read {}", root().display()))? {
+        let path = entry?.path();
+        let year = path.file_name()
+            .and_then(|name| name.to_str())
+            .filter(|name| name.len() == 4)
+            .and_then(|name| name.parse().ok());
+
+        if let Some(year) = year.filter(|_| path.is_dir()) {
+            years.push(year);
+        }
+    }
+    years.sort();
+
+    Ok(years)
+}
+
+/// The latest year with at least one day, which commands work on when no
+/// year is given.
+pub fn latest_year() -> anyhow::Result<u32> {
+    for year in years()?.into_iter().rev() {
+        if !Day::all(year)?.is_empty() {
+            return Ok(year);
+        }
+    }
+
+    bail!("No year has any days yet")
+}
+
+/// A value from the `[package]` section of a crate's manifest.
+pub fn manifest_field(dir: &Path, key: &str) -> anyhow::Result<Option<String>> {
+    let path = dir.join("Cargo.toml");
+    let manifest = std::fs::read_to_string(&path)
+        .with_context(|| format!("Could not read {}", path.display()))?;
+    let prefix = format!("{} = ", key);
+
+    Ok(manifest.lines()
+        .find_map(|line| line.strip_prefix(&prefix))
+        .map(|value| value.trim_matches('"').into()))
+}
+
+#[derive(Debug)]
+pub struct Day {
+    /// Name of the day's package and binary, see [`Puzzle::package`].
+    pub name: String,
+    pub dir: PathBuf,
+    pub puzzle: Puzzle,
+}
+
+impl Day {
+    pub fn new(year: u32, num: u32) -> anyhow::Result<Self> {
+        let dir = root().join(year.to_string()).join(format!("day{:02}", num));
+
+        ensure!(dir.join("Cargo.toml").exists(), "No crate for {} day {}", year, num);
+
+        Self::from_dir(dir)
+    }
+
+    fn from_dir(dir: PathBuf) -> anyhow::Result<Self> {
+        let puzzle = Puzzle::from_dir(&dir)?;
+        let name = manifest_field(&dir, "name")?
+            .with_context(|| format!("No package name in {}", dir.display()))?;
+        ensure!(
+            name == puzzle.package(),
+            "The package in {} is named {}, not {}",
+            dir.display(),
+            name,
+            puzzle.package(),
+        );
+
+        Ok(Self {name, dir, puzzle})
+    }
+
+    /// Every day crate of the year, in order.
+    pub fn all(year: u32) -> anyhow::Result<Vec<Self>> {
+        let dir = root().join(year.to_string());
+        let mut days = vec![];
+
+        ensure!(dir.is_dir(), "No directory for {}", year);
+
+        for entry in std::fs::read_dir(&dir)
+            .with_context(|| format!("Could not read {}", dir.display()))? {
+            let path = entry?.path();
+            let is_day = path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("day"));
+
+            if is_day && path.join("Cargo.toml").exists() {
+                days.push(Self::from_dir(path)?);
+            }
+        }
+        days.sort_by_key(|day| day.puzzle.day);
+
+        Ok(days)
+    }
+
+    /// The given days of the year, or every day when none are given.
+    pub fn select(year: u32, nums: &[u32]) -> anyhow::Result<Vec<Self>> {
+        if nums.is_empty() {
+            Self::all(year)
+        } else {
+            nums.iter().map(|&num| Self::new(year, num)).collect()
+        }
+    }
+
+    /// Build the release binary for this day.
+    pub fn build(&self) -> anyhow::Result<()> {
+        build(std::slice::from_ref(self))
+    }
+
+    /// The committed example inputs of this day.
+    pub fn examples(&self) -> anyhow::Result<Vec<Example>> {
+        fixture::examples(&self.dir.join("examples"))
+    }
+
+    /// The day's release binary, run from the day's directory.
+    pub fn command(&self) -> Command {
+        let mut command = Command::new(target_dir().join("release").join(&self.name));
+        command.current_dir(&self.dir);
+
+        command
+    }
+
+    /// Run the day's binary on `input` with timings enabled. A relative
+    /// `input` is taken from the current directory, not the day's.
+    pub fn run(&self, input: &Path, args: &[&str]) -> anyhow::Result<Run> {
+        let input = input.canonicalize()
+            .with_context(|| format!("Could not find {}", input.display()))?;
+        let start = Instant::now();
+        let output = self.command()
+            .arg(&input)
+            .arg("--time")
+            .args(args)
+            .output()
+            .with_context(|| format!("Could not run {}", self.name))?;
+        let wall = start.elapsed();
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+
+        let mut run = Run {wall, ..Run::default()};
+
+        // Days print part 1 on the first line and part 2 on the rest,
+        // which may span several lines (e.g. day 13's image)
+        if let Some((part1, part2)) = stdout.split_once('\n') {
+            run.part1 = Some(part1.into()).filter(|s: &String| !s.is_empty());
+            run.part2 = Some(part2.trim_end().into())
+                .filter(|s: &String| !s.is_empty());
+        }
+
+        for line in stderr.lines() {
+            if let Some((name, nanos)) = line.strip_prefix("time ")
+                .and_then(|l| l.strip_suffix(" ns"))
+                .and_then(|l| l.split_once(": ")) {
+                if let Ok(nanos) = nanos.parse() {
+                    run.times.push((name.into(), Duration::from_nanos(nanos)));
+                }
+            }
+        }
+
+        if !output.status.success() {
+            run.error = Some(error_message(&stderr));
+        }
+
+        Ok(run)
+    }
+}
+
+/// Build the release binaries of several days at once.
+pub fn build(days: &[Day]) -> anyhow::Result<()> {
+    // Without any packages cargo would build the whole workspace
+    if days.is_empty() {
+        return Ok(());
+    }
+
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let status = Command::new(cargo)
+        .current_dir(root())
+        .args(["build", "--release", "--quiet"])
+        .args(days.iter().flat_map(|day| ["--package", &day.name]))
+        .status()
+        .context("Could not run cargo")?;
+
+    ensure!(status.success(), "Failed to build the days");
+
+    Ok(())
+}
+
+// Pick the message out of an anyhow error or a panic,
+// skipping any backtrace that follows it
+fn error_message(stderr: &str) -> String {
+    let mut lines = stderr.lines();
+
+    while let Some(line) = lines.next() {
+        if let Some(error) = line.strip_prefix("Error: ") {
+            return error.into();
+        }
+        if line.contains("panicked at") {
+            return lines.next().unwrap_or(line).into();
+        }
+    }
+
+    String::from("Failed without an error message")
+}
+
+/// Answers and timings from a single run of a day.
+#[derive(Debug, Default)]
+pub struct Run {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+    pub times: Vec<(String, Duration)>,
+    pub wall: Duration,
+    pub error: Option<String>,
+}
+
+impl Run {
+    pub fn time(&self, phase: &str) -> Option<Duration> {
+        self.times.iter()
+            .find(|(name, _)| name == phase)
+            .map(|(_, time)| *time)
+    }
+
+    pub fn answers(&self) -> (Option<&str>, Option<&str>) {
+        (self.part1.as_deref(), self.part2.as_deref())
+    }
+}
@@ -0,0 +1,119 @@
+use std::path::PathBuf;
+
+use anyhow::{ensure, Context};
+
+use crate::day::Day;
+use crate::fixture::Answers;
+
+#[derive(Debug, clap::Args)]
+pub struct ExamplesArgs {
+    /// Day the puzzle page belongs to
+    day: u32,
+
+    /// Puzzle description saved from the browser as HTML
+    page: PathBuf,
+
+    /// Which <pre><code> block holds the example input, counting from 0
+    #[arg(long, default_value_t = 0)]
+    block: usize,
+
+    /// Name of the fixture in the day's examples directory
+    #[arg(long, default_value = "example")]
+    name: String,
+
+    /// Replace an existing fixture of the same name
+    #[arg(long)]
+    force: bool,
+}
+
+/// Text between every `start` and the following `end` in `s`.
+fn between<'a>(s: &'a str, start: &'a str, end: &'a str) -> impl Iterator<Item = &'a str> {
+    s.split(start)
+        .skip(1)
+        .filter_map(move |part| part.split_once(end).map(|(inner, _)| inner))
+}
+
+// Drop tags such as the <em> highlights inside examples
+// and decode the entities AoC pages use
+fn text(html: &str) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut in_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => out.push(c),
+            _ => {},
+        }
+    }
+
+    out.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+pub fn examples(year: u32, args: ExamplesArgs) -> anyhow::Result<()> {
+    let day = Day::new(year, args.day)?;
+    let html = aoc::input::read(&args.page)?;
+
+    // Each part of a puzzle is its own <article>; pages saved before
+    // solving part 1 only have one
+    let mut parts: Vec<&str> = between(&html, "<article", "</article>").collect();
+    if parts.is_empty() {
+        parts.push(&html);
+    }
+
+    let blocks: Vec<String> = parts.iter()
+        .flat_map(|part| between(part, "<pre><code>", "</code></pre>"))
+        .map(text)
+        .collect();
+    ensure!(!blocks.is_empty(), "No <pre><code> blocks in {}", args.page.display());
+
+    for (i, block) in blocks.iter().enumerate() {
+        println!(
+            "[{}] {} lines: {}",
+            i,
+            block.lines().count(),
+            block.lines().next().unwrap_or_default(),
+        );
+    }
+
+    // The example's answer is the last emphasized code in each part
+    let answer = |part: Option<&&str>| part
+        .and_then(|part| between(part, "<code><em>", "</em></code>").last())
+        .map(text);
+    let answers = Answers {
+        part1: answer(parts.first()),
+        part2: answer(parts.get(1)),
+    };
+
+    let block = blocks.get(args.block)
+        .with_context(|| format!("No block {}, the page has {}", args.block, blocks.len()))?;
+
+    let dir = day.dir.join("examples");
+    let input = dir.join(format!("{}.txt", args.name));
+    ensure!(
+        args.force || !input.exists(),
+        "{} already exists, use --force to replace it",
+        input.display(),
+    );
+
+    std::fs::create_dir_all(&dir)
+        .with_context(|| format!("Could not create {}", dir.display()))?;
+    std::fs::write(&input, aoc::input::normalize(block))
+        .with_context(|| format!("Could not write {}", input.display()))?;
+    println!("Wrote block {} to {}", args.block, input.display());
+
+    if answers != Answers::default() {
+        let path = input.with_extension("answers");
+        std::fs::write(&path, answers.to_string())
+            .with_context(|| format!("Could not write {}", path.display()))?;
+        print!("Wrote {}:\n{}", path.display(), answers);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{bail, Context};
+
+/// Expected answers of an example, saved next to it as `<name>.answers`:
+///
+/// ```text
+/// part1: 7
+/// part2: 5
+/// ```
+///
+/// Either part may be left out when the puzzle gives no answer for it.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl FromStr for Answers {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let mut answers = Self::default();
+
+        for line in s.lines().filter(|l| !l.trim().is_empty()) {
+            let (part, answer) = line.split_once(':')
+                .with_context(|| format!("Invalid answer: {}", line))?;
+            let answer = Some(answer.trim().into());
+
+            match part.trim() {
+                "part1" => answers.part1 = answer,
+                "part2" => answers.part2 = answer,
+                _ => bail!("Invalid part: {}", part),
+            }
+        }
+
+        Ok(answers)
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(answer) = &self.part1 {
+            writeln!(f, "part1: {}", answer)?;
+        }
+        if let Some(answer) = &self.part2 {
+            writeln!(f, "part2: {}", answer)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// An example input from a day's `examples` directory.
+#[derive(Debug)]
+pub struct Example {
+    pub name: String,
+    pub input: PathBuf,
+    pub answers: Option<Answers>,
+}
+
+/// Every example in `dir`, sorted by name.
+pub fn examples(dir: &Path) -> anyhow::Result<Vec<Example>> {
+    let mut examples = vec![];
+    if !dir.exists() {
+        return Ok(examples);
+    }
+
+    for entry in std::fs::read_dir(dir)
+        .with_context(|| format!("Could not read {}", dir.display()))? {
+        let input = entry?.path();
+        if input.extension().is_none_or(|ext| ext != "txt") {
+            continue;
+        }
+
+        let answers = input.with_extension("answers");
+        let answers = if answers.exists() {
+            Some(aoc::input::read(&answers)?.parse()
+                .with_context(|| format!("Invalid answers in {}", answers.display()))?)
+        } else {
+            None
+        };
+
+        examples.push(Example {
+            name: format!(
+                "examples/{}",
+                input.file_name().unwrap_or_default().to_string_lossy(),
+            ),
+            input,
+            answers,
+        });
+    }
+    examples.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(examples)
+}
@@ -0,0 +1,55 @@
+use std::path::PathBuf;
+
+use aoc::Puzzle;
+use anyhow::Context;
+
+use crate::day::Day;
+
+#[derive(Debug, clap::Subcommand)]
+pub enum InputCommand {
+    /// Copy a puzzle input into the input store
+    Add {
+        /// Day the input belongs to
+        day: u32,
+
+        /// File holding the input
+        file: PathBuf,
+
+        /// Encrypt the stored copy with the secret in AOC_SECRET
+        #[arg(long)]
+        encrypt: bool,
+    },
+
+    /// Move every dayNN/input.txt of the year left in the working tree
+    /// into the input store
+    Import {
+        /// Encrypt the stored copies with the secret in AOC_SECRET
+        #[arg(long)]
+        encrypt: bool,
+    },
+}
+
+pub fn input(year: u32, command: InputCommand) -> anyhow::Result<()> {
+    match command {
+        InputCommand::Add {day, file, encrypt} => {
+            let data = aoc::input::read(&file)?;
+            let path = Puzzle {year, day}.write(&data, encrypt)?;
+            println!("{} -> {}", file.display(), path.display());
+        },
+        InputCommand::Import {encrypt} => {
+            for day in Day::all(year)? {
+                let file = day.dir.join("input.txt");
+                if !file.exists() {
+                    continue;
+                }
+
+                let path = day.puzzle.write(&aoc::input::read(&file)?, encrypt)?;
+                std::fs::remove_file(&file)
+                    .with_context(|| format!("Could not remove {}", file.display()))?;
+                println!("{} -> {}", file.display(), path.display());
+            }
+        },
+    }
+
+    Ok(())
+}
@@ -0,0 +1,77 @@
+//! Runner for the day binaries.
+
+mod all;
+mod batch;
+mod check;
+mod day;
+mod examples;
+mod fixture;
+mod input;
+mod readme;
+mod repl;
+mod report;
+mod snapshot;
+
+use clap::{Parser, Subcommand};
+
+#[derive(Debug, Parser)]
+#[command(about = "Run and compare Advent of Code solutions")]
+struct Cli {
+    /// Year to work on, the latest year with any days by default
+    year: Option<u32>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Run every day of the year on its stored input and report
+    /// answers, timings and errors
+    All,
+
+    /// Run one day over every input file in a directory and report
+    /// answers, timings and errors
+    Batch(batch::BatchArgs),
+
+    /// Run every day on its examples and stored input, checking the
+    /// examples' answers and snapshots and that Windows line endings give
+    /// the same answers
+    Check(check::CheckArgs),
+
+    /// Save the example from a puzzle page as a fixture for `check`
+    Examples(examples::ExamplesArgs),
+
+    /// Manage the local input store
+    #[command(subcommand)]
+    Input(input::InputCommand),
+
+    /// Run every day and regenerate the progress table in README.md
+    Readme(readme::ReadmeArgs),
+
+    /// Step through a day's puzzle state interactively
+    Repl(repl::ReplArgs),
+
+    /// Record or accept snapshots of a day's output
+    #[command(subcommand)]
+    Snapshot(snapshot::SnapshotCommand),
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    let year = match cli.year {
+        Some(year) => year,
+        None => day::latest_year()?,
+    };
+
+    match cli.command {
+        Command::All => all::all(year),
+        Command::Batch(args) => batch::batch(year, args),
+        Command::Check(args) => check::check(year, args),
+        Command::Examples(args) => examples::examples(year, args),
+        Command::Input(command) => input::input(year, command),
+        Command::Readme(args) => readme::readme(args),
+        Command::Repl(args) => repl::repl(year, args),
+        Command::Snapshot(command) => snapshot::snapshot(year, command),
+    }
+}
@@ -0,0 +1,154 @@
+use std::path::Path;
+use std::time::Duration;
+
+use aoc::Puzzle;
+use anyhow::Context;
+
+use crate::day::{self, root, Day};
+use crate::report::Table;
+
+const START: &str = "<!-- progress:start -->";
+const END: &str = "<!-- progress:end -->";
+
+#[derive(Debug, clap::Args)]
+pub struct ReadmeArgs {
+    /// Print the table instead of writing it into README.md
+    #[arg(long)]
+    print: bool,
+}
+
+// Title of the puzzle, kept as the description of its crate
+fn title(day: &Day) -> anyhow::Result<String> {
+    Ok(day::manifest_field(&day.dir, "description")?.unwrap_or_default())
+}
+
+fn lines_of_code(dir: &Path) -> anyhow::Result<usize> {
+    let mut count = 0;
+
+    for entry in std::fs::read_dir(dir)
+        .with_context(|| format!("Could not read {}", dir.display()))? {
+        let path = entry?.path();
+
+        if path.is_dir() {
+            count += lines_of_code(&path)?;
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            count += std::fs::read_to_string(&path)?
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .count();
+        }
+    }
+
+    Ok(count)
+}
+
+fn runtime(time: Option<Duration>) -> String {
+    match time {
+        None => String::new(),
+        Some(t) if t < Duration::from_micros(1) => String::from("< 1 µs"),
+        Some(t) if t < Duration::from_millis(1) => format!("{} µs", t.as_micros()),
+        Some(t) if t < Duration::from_secs(1) => {
+            format!("{:.1} ms", t.as_secs_f64() * 1000.0)
+        },
+        Some(t) => format!("{:.2} s", t.as_secs_f64()),
+    }
+}
+
+// The progress of one year, run on the inputs in the store
+fn section(year: u32, days: &[Day], tmp: &Path) -> anyhow::Result<String> {
+    let mut table = Table::new(&["Day", "Title", "Stars", "Part 1", "Part 2", "Lines"]);
+    let mut stars = 0;
+
+    for num in 1 ..= 25 {
+        let puzzle = Puzzle {year, day: num};
+        let Some(day) = days.iter().find(|day| day.puzzle == puzzle) else {
+            table.push(vec![
+                num.to_string(), String::new(), "missing".into(),
+                String::new(), String::new(), String::new(),
+            ]);
+            continue;
+        };
+
+        let dir = day.dir.strip_prefix(root()).unwrap_or(&day.dir);
+        let link = format!("[{}]({})", num, dir.display());
+        let loc = lines_of_code(&day.dir.join("src"))?.to_string();
+
+        let Ok(data) = day.puzzle.read() else {
+            table.push(vec![
+                link, title(day)?, "no input".into(),
+                String::new(), String::new(), loc,
+            ]);
+            continue;
+        };
+
+        let input = tmp.join(format!("{}.txt", day.name));
+        std::fs::write(&input, data)
+            .with_context(|| format!("Could not write {}", input.display()))?;
+        let run = day.run(&input, &[])?;
+
+        // A part earns its star once it produces an answer
+        let earned = [&run.part1, &run.part2].iter()
+            .filter(|answer| answer.is_some())
+            .count();
+        stars += earned;
+
+        table.push(vec![
+            link,
+            title(day)?,
+            "⭐".repeat(earned),
+            runtime(run.time("part1")),
+            runtime(run.time("part2")),
+            loc,
+        ]);
+    }
+
+    Ok(format!("## {}: {} / 50 stars\n\n{}", year, stars, table.to_markdown()))
+}
+
+pub fn readme(args: ReadmeArgs) -> anyhow::Result<()> {
+    let mut days = vec![];
+    for year in day::years()? {
+        days.extend(Day::all(year)?);
+    }
+    day::build(&days)?;
+
+    // Years without any days yet are left out of the table
+    let mut years: Vec<u32> = days.iter().map(|day| day.puzzle.year).collect();
+    years.dedup();
+
+    let tmp = std::env::temp_dir().join(format!("aoc-readme-{}", std::process::id()));
+    std::fs::create_dir_all(&tmp)
+        .with_context(|| format!("Could not create {}", tmp.display()))?;
+
+    let mut sections = vec![];
+    for &year in &years {
+        sections.push(section(year, &days, &tmp)?);
+    }
+
+    std::fs::remove_dir_all(&tmp)
+        .with_context(|| format!("Could not remove {}", tmp.display()))?;
+
+    let section = format!("{}\n{}{}", START, sections.join("\n"), END);
+
+    if args.print {
+        println!("{}", section);
+        return Ok(());
+    }
+
+    let path = root().join("README.md");
+    let readme = std::fs::read_to_string(&path).unwrap_or_default();
+    let readme = match (readme.find(START), readme.find(END)) {
+        (Some(start), Some(end)) if start < end => format!(
+            "{}{}{}",
+            &readme[.. start],
+            section,
+            &readme[end + END.len() ..],
+        ),
+        _ => format!("{}{}\n", readme, section),
+    };
+    std::fs::write(&path, readme)
+        .with_context(|| format!("Could not write {}", path.display()))?;
+    println!("Updated {}", path.display());
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use std::path::PathBuf;
+
+use anyhow::{ensure, Context};
+
+use crate::day::Day;
+
+#[derive(Debug, clap::Args)]
+pub struct ReplArgs {
+    /// Day to explore, one of the days with a REPL
+    day: u32,
+
+    /// Input to load instead of the one in the input store
+    input: Option<PathBuf>,
+}
+
+pub fn repl(year: u32, args: ReplArgs) -> anyhow::Result<()> {
+    let day = Day::new(year, args.day)?;
+    day.build()?;
+
+    let mut command = day.command();
+    command.arg("--repl");
+    // The day runs from its own directory
+    if let Some(input) = &args.input {
+        command.arg(input.canonicalize()
+            .with_context(|| format!("Could not find {}", input.display()))?);
+    }
+
+    let status = command.status()
+        .with_context(|| format!("Could not run {}", day.name))?;
+    ensure!(status.success(), "{} exited with {}", day.name, status);
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use std::fmt::Write;
+use std::time::Duration;
+
+/// A table of results that can be written as CSV or Markdown.
+#[derive(Debug)]
+pub struct Table {
+    header: Vec<&'static str>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new(header: &[&'static str]) -> Self {
+        Self {header: header.to_vec(), rows: vec![]}
+    }
+
+    pub fn push(&mut self, row: Vec<String>) {
+        self.rows.push(row);
+    }
+
+    pub fn to_csv(&self) -> String {
+        let escape = |cell: &str| {
+            if cell.contains([',', '"', '\n']) {
+                format!("\"{}\"", cell.replace('"', "\"\""))
+            } else {
+                cell.into()
+            }
+        };
+
+        let mut out = String::new();
+        for row in std::iter::once(&self.header.iter().map(|h| h.to_string()).collect())
+            .chain(&self.rows) {
+            let row: Vec<_> = row.iter().map(|c| escape(c)).collect();
+            writeln!(out, "{}", row.join(",")).unwrap();
+        }
+
+        out
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let escape = |cell: &str| cell.replace('|', "\\|").replace('\n', "<br>");
+
+        let mut out = String::new();
+        writeln!(out, "| {} |", self.header.join(" | ")).unwrap();
+        writeln!(out, "|{}", "---|".repeat(self.header.len())).unwrap();
+        for row in &self.rows {
+            let row: Vec<_> = row.iter().map(|c| escape(c)).collect();
+            writeln!(out, "| {} |", row.join(" | ")).unwrap();
+        }
+
+        out
+    }
+}
+
+pub fn millis(time: Option<Duration>) -> String {
+    time.map(|t| format!("{:.3}", t.as_secs_f64() * 1000.0))
+        .unwrap_or_default()
+}
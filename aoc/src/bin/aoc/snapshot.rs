@@ -0,0 +1,171 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, ensure, Context};
+use similar::TextDiff;
+
+use crate::day::{self, Day};
+
+const SEPARATOR: &str = "---\n";
+
+/// Output of a day's binary saved in `snapshots/<name>.snap`:
+///
+/// ```text
+/// args: examples/example.txt --show 1,2
+/// ---
+/// <expected stdout>
+/// ```
+///
+/// The arguments are split on whitespace and the binary runs from the
+/// day's directory.
+#[derive(Debug)]
+pub struct Snapshot {
+    pub name: String,
+    pub path: PathBuf,
+    pub args: Vec<String>,
+    pub output: String,
+}
+
+impl Snapshot {
+    fn load(path: PathBuf) -> anyhow::Result<Self> {
+        let data = aoc::input::read(&path)?;
+        let (header, output) = data.split_once(SEPARATOR)
+            .with_context(|| format!("Missing {:?} in {}", SEPARATOR, path.display()))?;
+        let args = header.trim_end()
+            .strip_prefix("args:")
+            .with_context(|| format!("Missing args: in {}", path.display()))?
+            .split_whitespace()
+            .map(String::from)
+            .collect();
+
+        Ok(Self {
+            name: format!(
+                "snapshots/{}",
+                path.file_name().unwrap_or_default().to_string_lossy(),
+            ),
+            path,
+            args,
+            output: output.into(),
+        })
+    }
+
+    fn save(&self) -> anyhow::Result<()> {
+        let data = format!("args: {}\n{}{}", self.args.join(" "), SEPARATOR, self.output);
+
+        std::fs::write(&self.path, data)
+            .with_context(|| format!("Could not write {}", self.path.display()))
+    }
+
+    /// Run the day with the snapshot's arguments and return its output.
+    pub fn run(&self, day: &Day) -> anyhow::Result<String> {
+        let output = day.command()
+            .args(&self.args)
+            .output()
+            .with_context(|| format!("Could not run {}", day.name))?;
+
+        ensure!(
+            output.status.success(),
+            "{} failed: {}",
+            day.name,
+            String::from_utf8_lossy(&output.stderr).lines().next().unwrap_or_default(),
+        );
+
+        Ok(String::from_utf8(output.stdout)?)
+    }
+
+    /// Compare `actual` with the stored output, failing with a diff of
+    /// the two.
+    pub fn compare(&self, actual: &str) -> anyhow::Result<()> {
+        if actual == self.output {
+            return Ok(());
+        }
+
+        let diff = TextDiff::from_lines(self.output.as_str(), actual);
+        bail!(
+            "output differs, run `aoc snapshot accept` if it is right\n{}",
+            diff.unified_diff().header("expected", "actual"),
+        )
+    }
+}
+
+/// Every snapshot of a day, sorted by name.
+pub fn snapshots(day: &Day) -> anyhow::Result<Vec<Snapshot>> {
+    let dir = day.dir.join("snapshots");
+    let mut snapshots = vec![];
+    if !dir.exists() {
+        return Ok(snapshots);
+    }
+
+    for entry in std::fs::read_dir(&dir)
+        .with_context(|| format!("Could not read {}", dir.display()))? {
+        let path = entry?.path();
+
+        if path.extension().is_some_and(|ext| ext == "snap") {
+            snapshots.push(Snapshot::load(path)?);
+        }
+    }
+    snapshots.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(snapshots)
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum SnapshotCommand {
+    /// Record a new snapshot of a day's output
+    Add {
+        /// Day to snapshot
+        day: u32,
+
+        /// Name of the snapshot
+        name: String,
+
+        /// Arguments to run the day with
+        #[arg(last = true, required = true)]
+        args: Vec<String>,
+    },
+
+    /// Replace stored snapshots with the current output
+    Accept {
+        /// Days to accept, all of them when empty
+        days: Vec<u32>,
+    },
+}
+
+fn accept(day: &Day, mut snapshot: Snapshot) -> anyhow::Result<()> {
+    let actual = snapshot.run(day)?;
+
+    if actual != snapshot.output {
+        snapshot.output = actual;
+        snapshot.save()?;
+        println!("Accepted {} {}", day.name, snapshot.name);
+    }
+
+    Ok(())
+}
+
+pub fn snapshot(year: u32, command: SnapshotCommand) -> anyhow::Result<()> {
+    match command {
+        SnapshotCommand::Add {day, name, args} => {
+            let day = Day::new(year, day)?;
+            day.build()?;
+
+            let path = day.dir.join("snapshots").join(format!("{}.snap", name));
+            ensure!(!path.exists(), "{} already exists", path.display());
+            std::fs::create_dir_all(path.parent().unwrap_or(Path::new(".")))?;
+
+            let name = format!("snapshots/{}.snap", name);
+            accept(&day, Snapshot {name, path, args, output: String::new()})
+        },
+        SnapshotCommand::Accept {days} => {
+            let days = Day::select(year, &days)?;
+            day::build(&days)?;
+
+            for day in &days {
+                for snapshot in snapshots(day)? {
+                    accept(day, snapshot)?;
+                }
+            }
+
+            Ok(())
+        },
+    }
+}
@@ -0,0 +1,304 @@
+//! Puzzle inputs, kept out of the repository in a local input store.
+//!
+//! Puzzle authors ask that inputs are not published, so only the bundled
+//! examples are committed. Real inputs live in a git-ignored store laid out
+//! as `<store>/<year>/dayNN.txt`. The store defaults to `inputs/` at the
+//! root of the workspace and can be moved with [`STORE_VAR`].
+//!
+//! An input may instead be saved as `dayNN.txt.enc`, encrypted with a key
+//! derived from the secret in [`SECRET_VAR`] by Argon2id with a random salt
+//! per file. The secret should still be a long random value, such as the
+//! output of `openssl rand -hex 32`, as anyone with an encrypted file can
+//! try guesses at it offline.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, ensure, Context};
+use argon2::Argon2;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+
+/// Environment variable overriding the input store directory.
+pub const STORE_VAR: &str = "AOC_INPUTS";
+
+/// Environment variable holding the secret for encrypted inputs.
+pub const SECRET_VAR: &str = "AOC_SECRET";
+
+const MAGIC: &[u8] = b"AOC1";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+
+/// A single day's puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Puzzle {
+    /// The event, such as 2021.
+    pub year: u32,
+    /// The day of December, 1 to 25.
+    pub day: u32,
+}
+
+/// The [`Puzzle`] for the crate this is expanded in, taken from its
+/// package name, see [`Puzzle::package`].
+#[macro_export]
+macro_rules! puzzle {
+    () => {
+        $crate::input::Puzzle::from_package(env!("CARGO_PKG_NAME"))
+            .expect("Day packages are named dayNN-YYYY")
+    };
+}
+
+impl Puzzle {
+    /// Name of the day's package and binary, `dayNN-YYYY`. Every year has
+    /// a `day01`, so the year keeps the names unique in the workspace, and
+    /// the library is imported as `dayNN_YYYY`.
+    pub fn package(&self) -> String {
+        format!("day{:02}-{}", self.day, self.year)
+    }
+
+    /// The puzzle of a package named as [`Puzzle::package`] names it.
+    pub fn from_package(name: &str) -> anyhow::Result<Self> {
+        let (day, year) = name.strip_prefix("day")
+            .and_then(|name| name.split_once('-'))
+            .with_context(|| format!("Invalid day package: {}", name))?;
+
+        Ok(Self {
+            year: year.parse()
+                .with_context(|| format!("Invalid year: {}", year))?,
+            day: day.parse()
+                .with_context(|| format!("Invalid day: {}", day))?,
+        })
+    }
+
+    /// The puzzle of a day crate in `<year>/dayNN`.
+    pub fn from_dir(dir: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let dir = dir.as_ref();
+        let name = |path: Option<&Path>| path
+            .and_then(Path::file_name)
+            .and_then(|s| s.to_str())
+            .map(String::from)
+            .with_context(|| format!("Invalid day directory: {}", dir.display()));
+
+        let day = name(Some(dir))?;
+        let year = name(dir.parent())?;
+
+        Ok(Self {
+            year: year.parse()
+                .with_context(|| format!("Invalid year: {}", year))?,
+            day: day.strip_prefix("day")
+                .and_then(|d| d.parse().ok())
+                .with_context(|| format!("Invalid day: {}", day))?,
+        })
+    }
+
+    fn file_name(&self) -> String {
+        format!("day{:02}.txt", self.day)
+    }
+
+    /// Path of the plain text input in the store.
+    pub fn path(&self) -> PathBuf {
+        store().join(self.year.to_string()).join(self.file_name())
+    }
+
+    /// Path of the encrypted input in the store.
+    pub fn encrypted_path(&self) -> PathBuf {
+        store().join(self.year.to_string())
+            .join(self.file_name() + ".enc")
+    }
+
+    /// Read this puzzle's input from the store.
+    pub fn read(&self) -> anyhow::Result<String> {
+        let path = self.path();
+        if path.exists() {
+            return read(&path);
+        }
+
+        let path = self.encrypted_path();
+        if path.exists() {
+            let data = std::fs::read(&path)
+                .with_context(|| format!("Could not read {}", path.display()))?;
+            let data = decrypt(&secret()?, &data)
+                .with_context(|| format!("Could not decrypt {}", path.display()))?;
+
+            return String::from_utf8(data)
+                .with_context(|| format!("{} is not UTF-8", path.display()));
+        }
+
+        bail!(
+            "No input for {} day {}: add it with `aoc {} input add {} <file>` \
+            or save it as {}",
+            self.year,
+            self.day,
+            self.year,
+            self.day,
+            self.path().display(),
+        )
+    }
+
+    /// Save `data` as this puzzle's input, replacing any existing copy.
+    pub fn write(&self, data: &str, encrypt: bool) -> anyhow::Result<PathBuf> {
+        let (path, stale) = if encrypt {
+            (self.encrypted_path(), self.path())
+        } else {
+            (self.path(), self.encrypted_path())
+        };
+        let data = if encrypt {
+            self::encrypt(&secret()?, data.as_bytes())?
+        } else {
+            data.as_bytes().to_vec()
+        };
+
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Could not create {}", dir.display()))?;
+        }
+        std::fs::write(&path, data)
+            .with_context(|| format!("Could not write {}", path.display()))?;
+        if stale.exists() {
+            std::fs::remove_file(&stale)
+                .with_context(|| format!("Could not remove {}", stale.display()))?;
+        }
+
+        Ok(path)
+    }
+}
+
+/// Root of the workspace, one level above the `aoc` crate.
+pub fn root() -> &'static Path {
+    let manifest = Path::new(env!("CARGO_MANIFEST_DIR"));
+    manifest.parent().unwrap_or(manifest)
+}
+
+/// Directory of the input store.
+pub fn store() -> PathBuf {
+    std::env::var_os(STORE_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| root().join("inputs"))
+}
+
+/// Clean up an input the way an editor may have saved it: drop a byte
+/// order mark, turn CRLF line endings into LF and end on a single newline
+/// rather than on blank lines. Spaces are kept, as some inputs line up
+/// columns with them.
+pub fn normalize(data: &str) -> String {
+    let data = data.strip_prefix('\u{feff}').unwrap_or(data)
+        .replace("\r\n", "\n");
+    let data = data.trim_end_matches('\n');
+
+    if data.is_empty() {
+        String::new()
+    } else {
+        format!("{}\n", data)
+    }
+}
+
+/// Read a plain text input file.
+pub fn read(path: &Path) -> anyhow::Result<String> {
+    std::fs::read_to_string(path)
+        .with_context(|| format!("Could not read {}", path.display()))
+}
+
+fn secret() -> anyhow::Result<String> {
+    std::env::var(SECRET_VAR)
+        .with_context(|| format!("{} must be set for encrypted inputs", SECRET_VAR))
+}
+
+fn cipher(secret: &str, salt: &[u8]) -> anyhow::Result<XChaCha20Poly1305> {
+    let mut key = [0; 32];
+    Argon2::default().hash_password_into(secret.as_bytes(), salt, &mut key)
+        .map_err(|e| anyhow::anyhow!("Key derivation failed: {}", e))?;
+
+    Ok(XChaCha20Poly1305::new(&key.into()))
+}
+
+/// Encrypt `data` as `AOC1 || salt || nonce || ciphertext`.
+pub fn encrypt(secret: &str, data: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let mut salt = [0; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher(secret, &salt)?.encrypt(&nonce, data)
+        .map_err(|_| anyhow::anyhow!("Encryption failed"))?;
+
+    Ok([MAGIC, &salt, nonce.as_slice(), &ciphertext].concat())
+}
+
+/// Decrypt what [`encrypt`] wrote.
+pub fn decrypt(secret: &str, data: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let data = data.strip_prefix(MAGIC).context("Not an encrypted input")?;
+    ensure!(data.len() > SALT_LEN + NONCE_LEN, "Encrypted input is truncated");
+
+    let (salt, data) = data.split_at(SALT_LEN);
+    let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+    cipher(secret, salt)?.decrypt(XNonce::from_slice(nonce), ciphertext)
+        .map_err(|_| anyhow::anyhow!("Wrong secret or corrupted input"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_bom() {
+        assert_eq!(normalize("\u{feff}199\n200\n"), "199\n200\n");
+    }
+
+    #[test]
+    fn normalize_crlf() {
+        assert_eq!(normalize("199\r\n200\r\n"), "199\n200\n");
+        assert_eq!(normalize("199\r\n200"), "199\n200\n");
+    }
+
+    #[test]
+    fn normalize_trailing_blank_lines() {
+        assert_eq!(normalize("199\n200\n\n\n"), "199\n200\n");
+        assert_eq!(normalize("\u{feff}199\r\n200\r\n\r\n"), "199\n200\n");
+        assert_eq!(normalize("\n\n"), "");
+    }
+
+    #[test]
+    fn normalize_keeps_spaces() {
+        let data = "    [D]    \n[N] [C]    \n 1   2   3 \n";
+
+        assert_eq!(normalize(data), data);
+        assert_eq!(normalize(&data.replace('\n', "\r\n")), data);
+    }
+
+    #[test]
+    fn round_trip() {
+        let data = encrypt("secret", b"199\n200\n").unwrap();
+
+        assert!(data.starts_with(MAGIC));
+        assert_eq!(decrypt("secret", &data).unwrap(), b"199\n200\n");
+    }
+
+    #[test]
+    fn salted() {
+        assert_ne!(encrypt("secret", b"199\n").unwrap(), encrypt("secret", b"199\n").unwrap());
+    }
+
+    #[test]
+    fn wrong_secret() {
+        let data = encrypt("secret", b"199\n200\n").unwrap();
+        let e = decrypt("guess", &data).unwrap_err();
+
+        assert_eq!(e.to_string(), "Wrong secret or corrupted input");
+    }
+
+    #[test]
+    fn truncated() {
+        let data = encrypt("secret", b"199\n200\n").unwrap();
+
+        let e = decrypt("secret", &data[.. MAGIC.len() + SALT_LEN + NONCE_LEN]).unwrap_err();
+        assert_eq!(e.to_string(), "Encrypted input is truncated");
+
+        let e = decrypt("secret", &data[.. data.len() - 1]).unwrap_err();
+        assert_eq!(e.to_string(), "Wrong secret or corrupted input");
+    }
+
+    #[test]
+    fn not_encrypted() {
+        let e = decrypt("secret", b"199\n200\n").unwrap_err();
+
+        assert_eq!(e.to_string(), "Not an encrypted input");
+    }
+}
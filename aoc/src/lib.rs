@@ -0,0 +1,103 @@
+//! Helpers shared by every day's solution binary.
+
+#[cfg(feature = "alloc")]
+mod alloc;
+pub mod input;
+pub mod num;
+pub mod phase;
+pub mod repl;
+pub mod trace;
+
+use std::path::PathBuf;
+
+use anyhow::{bail, ensure, Context};
+use clap::Parser;
+
+pub use input::Puzzle;
+pub use phase::phase;
+
+/// Command line arguments understood by every day.
+#[derive(Debug, Parser)]
+#[command(about = None, long_about = None)]
+pub struct Args {
+    /// Puzzle input to solve instead of the one in the input store
+    pub input: Option<PathBuf>,
+
+    /// Alternative implementation to run, for days that have more than one
+    #[arg(long)]
+    pub variant: Option<String>,
+
+    /// Report how long parsing and each part took on stderr
+    #[arg(long)]
+    pub time: bool,
+
+    /// Report allocations and peak heap memory of each phase on stderr
+    #[cfg(feature = "alloc")]
+    #[arg(long)]
+    pub alloc: bool,
+
+    /// Explore the puzzle's state interactively instead of solving it,
+    /// on days that have a REPL
+    #[arg(long)]
+    pub repl: bool,
+
+    /// Log solver internals to stderr (-v for debug, -vv for trace)
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    pub verbose: u8,
+}
+
+impl Args {
+    /// Set up logging, timing and allocation counting as requested.
+    pub fn setup(&self) {
+        trace::init(self.verbose);
+        phase::report_times(self.time);
+        #[cfg(feature = "alloc")]
+        alloc::report_allocs(self.alloc);
+    }
+
+    /// Read the input file given on the command line, falling back to the
+    /// puzzle's input in the input store, and normalize it.
+    ///
+    /// Days with a REPL start it with [`repl::run`] before getting here,
+    /// so `--repl` is an error for the rest.
+    pub fn input(&self, puzzle: Puzzle) -> anyhow::Result<String> {
+        ensure!(!self.repl, "There is no REPL for this day");
+
+        self.read(puzzle)
+    }
+
+    pub(crate) fn read(&self, puzzle: Puzzle) -> anyhow::Result<String> {
+        let data = match &self.input {
+            Some(path) => input::read(path)?,
+            None => puzzle.read()?,
+        };
+
+        Ok(input::normalize(&data))
+    }
+
+    /// Check the requested variant against the ones a day knows about.
+    /// The first entry of `known` is the default.
+    pub fn variant<'a>(&'a self, known: &[&'a str]) -> anyhow::Result<&'a str> {
+        match self.variant.as_deref() {
+            None => known.first().copied().context("No variants given"),
+            Some(v) if known.contains(&v) => Ok(v),
+            Some(v) => bail!(
+                "Unknown variant: {} (expected one of {})",
+                v,
+                known.join(", "),
+            ),
+        }
+    }
+}
+
+/// Parse the command line and set up logging.
+///
+/// Every day's `main` should call this before doing any work. Days with
+/// options of their own flatten [`Args`] into their parser and call
+/// [`Args::setup`] instead.
+pub fn init() -> Args {
+    let args = Args::parse();
+    args.setup();
+
+    args
+}
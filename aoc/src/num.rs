@@ -0,0 +1,417 @@
+//! Integer types for the `Num` alias of each day.
+//!
+//! By default [`I32`], [`I64`], [`U32`] and [`U64`] are the primitives they
+//! are named after, so solutions pay nothing for them. Two cargo features
+//! change that for every day at once:
+//!
+//! - `checked` wraps them in [`Checked`], which panics with the operation
+//!   and its operands as soon as a result does not fit, instead of
+//!   silently wrapping in release builds.
+//! - `wide` widens them all to 128 bits, to tell an overflow apart from
+//!   a bug in the solution.
+//!
+//! Both together check the 128 bit types.
+//!
+//! ```text
+//! cargo run --release -p day07-2021 --features aoc/checked
+//! ```
+
+use std::fmt;
+use std::iter::{Product, Sum};
+use std::ops::{
+    Add, AddAssign, BitAnd, BitOr, BitXor, Div, DivAssign, Mul, MulAssign, Neg,
+    Rem, RemAssign, Shl, Sub, SubAssign,
+};
+use std::str::FromStr;
+
+#[cfg(not(feature = "wide"))]
+mod base {
+    /// A `Num` for values that fit in `i32`.
+    pub type I32 = i32;
+    /// A `Num` for values that fit in `i64`.
+    pub type I64 = i64;
+    /// A `Num` for values that fit in `u32`.
+    pub type U32 = u32;
+    /// A `Num` for values that fit in `u64`.
+    pub type U64 = u64;
+}
+
+#[cfg(feature = "wide")]
+mod base {
+    /// A `Num` for values that fit in `i32`.
+    pub type I32 = i128;
+    /// A `Num` for values that fit in `i64`.
+    pub type I64 = i128;
+    /// A `Num` for values that fit in `u32`.
+    pub type U32 = u128;
+    /// A `Num` for values that fit in `u64`.
+    pub type U64 = u128;
+}
+
+#[cfg(not(feature = "checked"))]
+pub use base::{I32, I64, U32, U64};
+
+/// A `Num` for values that fit in `i32`.
+#[cfg(feature = "checked")]
+pub type I32 = Checked<base::I32>;
+/// A `Num` for values that fit in `i64`.
+#[cfg(feature = "checked")]
+pub type I64 = Checked<base::I64>;
+/// A `Num` for values that fit in `u32`.
+#[cfg(feature = "checked")]
+pub type U32 = Checked<base::U32>;
+/// A `Num` for values that fit in `u64`.
+#[cfg(feature = "checked")]
+pub type U64 = Checked<base::U64>;
+
+/// Conversions between a `Num` and the primitive it is built on, which
+/// take the place of `as` casts so the same code compiles in every mode.
+pub trait Int: Copy {
+    /// The primitive, `i32` for [`I32`] unless widened.
+    type Prim;
+
+    /// Wrap a primitive.
+    fn new(prim: Self::Prim) -> Self;
+
+    /// The primitive inside.
+    fn get(self) -> Self::Prim;
+
+    /// Convert any other integer, such as a length or an index, panicking
+    /// if it does not fit where an `as` cast would silently truncate.
+    #[track_caller]
+    fn of<T>(value: T) -> Self
+    where
+        T: Copy + fmt::Display,
+        Self::Prim: TryFrom<T>,
+    {
+        match Self::Prim::try_from(value) {
+            Ok(prim) => Self::new(prim),
+            Err(_) => panic!(
+                "checked conversion: {} does not fit in {}",
+                value,
+                std::any::type_name::<Self::Prim>(),
+            ),
+        }
+    }
+}
+
+/// An integer whose arithmetic panics on overflow in every build profile,
+/// naming the operation that overflowed.
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Checked<T>(T);
+
+#[cold]
+#[track_caller]
+fn overflow(expr: fmt::Arguments<'_>, ty: &str) -> ! {
+    panic!("checked arithmetic: {} has no {} result", expr, ty)
+}
+
+impl<T: fmt::Display> fmt::Display for Checked<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Checked<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: FromStr> FromStr for Checked<T> {
+    type Err = T::Err;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Self)
+    }
+}
+
+impl<T> From<T> for Checked<T> {
+    fn from(prim: T) -> Self {
+        Self(prim)
+    }
+}
+
+impl<T: Copy> Int for Checked<T> {
+    type Prim = T;
+
+    fn new(prim: T) -> Self {
+        Self(prim)
+    }
+
+    fn get(self) -> T {
+        self.0
+    }
+}
+
+macro_rules! checked_op {
+    ($t:ty, $trait:ident, $method:ident, $assign:ident, $assign_method:ident, $checked:ident, $op:literal) => {
+        impl $trait for Checked<$t> {
+            type Output = Self;
+
+            #[track_caller]
+            fn $method(self, rhs: Self) -> Self {
+                match self.0.$checked(rhs.0) {
+                    Some(out) => Self(out),
+                    None => overflow(
+                        format_args!(concat!("{} ", $op, " {}"), self.0, rhs.0),
+                        stringify!($t),
+                    ),
+                }
+            }
+        }
+
+        impl $trait<$t> for Checked<$t> {
+            type Output = Self;
+
+            #[track_caller]
+            fn $method(self, rhs: $t) -> Self {
+                self.$method(Self(rhs))
+            }
+        }
+
+        impl $trait<&Self> for Checked<$t> {
+            type Output = Self;
+
+            #[track_caller]
+            fn $method(self, rhs: &Self) -> Self {
+                self.$method(*rhs)
+            }
+        }
+
+        impl $trait<Checked<$t>> for &Checked<$t> {
+            type Output = Checked<$t>;
+
+            #[track_caller]
+            fn $method(self, rhs: Checked<$t>) -> Checked<$t> {
+                (*self).$method(rhs)
+            }
+        }
+
+        impl $trait for &Checked<$t> {
+            type Output = Checked<$t>;
+
+            #[track_caller]
+            fn $method(self, rhs: Self) -> Checked<$t> {
+                (*self).$method(*rhs)
+            }
+        }
+
+        impl $assign for Checked<$t> {
+            #[track_caller]
+            fn $assign_method(&mut self, rhs: Self) {
+                *self = self.$method(rhs);
+            }
+        }
+
+        impl $assign<$t> for Checked<$t> {
+            #[track_caller]
+            fn $assign_method(&mut self, rhs: $t) {
+                *self = self.$method(Self(rhs));
+            }
+        }
+    };
+}
+
+macro_rules! bit_op {
+    ($t:ty, $trait:ident, $method:ident, $op:tt) => {
+        impl $trait for Checked<$t> {
+            type Output = Self;
+
+            fn $method(self, rhs: Self) -> Self {
+                Self(self.0 $op rhs.0)
+            }
+        }
+
+        impl $trait<$t> for Checked<$t> {
+            type Output = Self;
+
+            fn $method(self, rhs: $t) -> Self {
+                Self(self.0 $op rhs)
+            }
+        }
+    };
+}
+
+macro_rules! checked {
+    ($($t:ty),*) => {$(
+        impl Checked<$t> {
+            /// The smallest value.
+            pub const MIN: Self = Self(<$t>::MIN);
+            /// The largest value.
+            pub const MAX: Self = Self(<$t>::MAX);
+        }
+
+        impl Int for $t {
+            type Prim = $t;
+
+            fn new(prim: $t) -> Self {
+                prim
+            }
+
+            fn get(self) -> $t {
+                self
+            }
+        }
+
+        checked_op!($t, Add, add, AddAssign, add_assign, checked_add, "+");
+        checked_op!($t, Sub, sub, SubAssign, sub_assign, checked_sub, "-");
+        checked_op!($t, Mul, mul, MulAssign, mul_assign, checked_mul, "*");
+        checked_op!($t, Div, div, DivAssign, div_assign, checked_div, "/");
+        checked_op!($t, Rem, rem, RemAssign, rem_assign, checked_rem, "%");
+
+        bit_op!($t, BitAnd, bitand, &);
+        bit_op!($t, BitOr, bitor, |);
+        bit_op!($t, BitXor, bitxor, ^);
+
+        impl Neg for Checked<$t> {
+            type Output = Self;
+
+            #[track_caller]
+            fn neg(self) -> Self {
+                match self.0.checked_neg() {
+                    Some(out) => Self(out),
+                    None => overflow(format_args!("-({})", self.0), stringify!($t)),
+                }
+            }
+        }
+
+        // Shifting out set bits loses them as surely as an overflowing
+        // multiplication, so that counts as overflow too
+        impl Shl<u32> for Checked<$t> {
+            type Output = Self;
+
+            #[track_caller]
+            fn shl(self, rhs: u32) -> Self {
+                match self.0.checked_shl(rhs) {
+                    Some(out) if out >> rhs == self.0 => Self(out),
+                    _ => overflow(format_args!("{} << {}", self.0, rhs), stringify!($t)),
+                }
+            }
+        }
+
+        impl PartialEq<$t> for Checked<$t> {
+            fn eq(&self, other: &$t) -> bool {
+                self.0 == *other
+            }
+        }
+
+        impl PartialOrd<$t> for Checked<$t> {
+            fn partial_cmp(&self, other: &$t) -> Option<std::cmp::Ordering> {
+                self.0.partial_cmp(other)
+            }
+        }
+
+        impl Sum for Checked<$t> {
+            #[track_caller]
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(Self(0), |a, b| a + b)
+            }
+        }
+
+        impl<'a> Sum<&'a Self> for Checked<$t> {
+            #[track_caller]
+            fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+                iter.fold(Self(0), |a, b| a + *b)
+            }
+        }
+
+        impl Product for Checked<$t> {
+            #[track_caller]
+            fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(Self(1), |a, b| a * b)
+            }
+        }
+
+        impl<'a> Product<&'a Self> for Checked<$t> {
+            #[track_caller]
+            fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+                iter.fold(Self(1), |a, b| a * *b)
+            }
+        }
+    )*};
+}
+
+checked!(i32, i64, i128, u32, u64, u128);
+
+macro_rules! checked_signed {
+    ($($t:ty),*) => {$(
+        impl Checked<$t> {
+            /// The absolute value, panicking for the minimum.
+            #[track_caller]
+            pub fn abs(self) -> Self {
+                match self.0.checked_abs() {
+                    Some(out) => Self(out),
+                    None => overflow(format_args!("abs({})", self.0), stringify!($t)),
+                }
+            }
+        }
+    )*};
+}
+
+checked_signed!(i32, i64, i128);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "checked arithmetic: 2147483647 + 1 has no i32 result")]
+    fn add_overflow() {
+        let _ = Checked(i32::MAX) + Checked(1);
+    }
+
+    #[test]
+    #[should_panic(expected = "checked arithmetic: 4294967295 * 2 has no u32 result")]
+    fn mul_overflow() {
+        let _ = Checked(u32::MAX) * 2;
+    }
+
+    #[test]
+    #[should_panic(expected = "checked arithmetic: -(-9223372036854775808) has no i64 result")]
+    fn neg_overflow() {
+        let _ = -Checked(i64::MIN);
+    }
+
+    #[test]
+    #[should_panic(expected = "checked arithmetic: abs(-2147483648) has no i32 result")]
+    fn abs_overflow() {
+        let _ = Checked(i32::MIN).abs();
+    }
+
+    #[test]
+    fn shl() {
+        assert_eq!(Checked(3u32) << 30, 3 << 30);
+        assert_eq!(Checked(-1i32) << 31, i32::MIN);
+    }
+
+    #[test]
+    #[should_panic(expected = "checked arithmetic: 3 << 31 has no u32 result")]
+    fn shl_loses_bits() {
+        let _ = Checked(3u32) << 31;
+    }
+
+    #[test]
+    #[should_panic(expected = "checked arithmetic: 1 << 32 has no u32 result")]
+    fn shl_too_far() {
+        let _ = Checked(1u32) << 32;
+    }
+
+    #[test]
+    fn of() {
+        assert_eq!(Checked::<u64>::of(7usize), 7);
+        assert_eq!(<i32 as Int>::of(-7i64), -7);
+    }
+
+    #[test]
+    #[should_panic(expected = "checked conversion: -1 does not fit in u32")]
+    fn of_negative() {
+        let _ = <u32 as Int>::of(-1i64);
+    }
+
+    #[test]
+    #[should_panic(expected = "checked conversion: 4294967296 does not fit in i32")]
+    fn of_too_large() {
+        let _ = Checked::<i32>::of(1u64 << 32);
+    }
+}
@@ -0,0 +1,37 @@
+//! Timing of the parse, part 1 and part 2 phases of a solution.
+//!
+//! With `--time` every phase prints one line to stderr in the form
+//! `time <name>: <nanoseconds> ns`, which the runner picks up. Builds with
+//! the `alloc` feature can report allocations the same way, see `--alloc`.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+use tracing::debug_span;
+
+static REPORT: AtomicBool = AtomicBool::new(false);
+
+pub(crate) fn report_times(enabled: bool) {
+    REPORT.store(enabled, Ordering::Relaxed);
+}
+
+/// Run `f` as the named phase of a solution, inside a tracing span of the
+/// same name.
+pub fn phase<T>(name: &str, f: impl FnOnce() -> T) -> T {
+    let _span = debug_span!("phase", name).entered();
+    #[cfg(feature = "alloc")]
+    let mark = crate::alloc::Mark::start();
+    let start = Instant::now();
+    let out = f();
+    let elapsed = start.elapsed();
+
+    if REPORT.load(Ordering::Relaxed) {
+        eprintln!("time {}: {} ns", name, elapsed.as_nanos());
+    }
+    #[cfg(feature = "alloc")]
+    if let Some(mark) = mark {
+        mark.report(name);
+    }
+
+    out
+}
@@ -0,0 +1,128 @@
+//! Interactive exploration of a day's state, started with `--repl`.
+//!
+//! Every day with a REPL implements [`Repl`] for its model and the shared
+//! loop here provides the commands:
+//!
+//! ```text
+//! step [n]        advance the model n steps (1 by default)
+//! show            print the current state
+//! reset           reload the state from the input
+//! query <q> ...   ask a day specific question, see help
+//! help            list the commands and queries
+//! quit            leave, as does end of input
+//! ```
+
+use std::io::{self, BufRead, IsTerminal, Write};
+
+use anyhow::{bail, Context};
+
+use crate::{Args, Puzzle};
+
+/// A day's state that can be stepped through in the REPL.
+pub trait Repl: Sized {
+    /// What one step of the model is, for `help`.
+    const STEP: &'static str;
+
+    /// Queries understood by [`Repl::query`] with their descriptions,
+    /// for `help`.
+    const QUERIES: &'static [(&'static str, &'static str)];
+
+    /// Build the initial state from the puzzle input.
+    fn load(input: &str) -> anyhow::Result<Self>;
+
+    /// Advance the model one step and describe what happened.
+    fn step(&mut self) -> anyhow::Result<String>;
+
+    /// Describe the whole current state.
+    fn show(&self) -> String;
+
+    /// Answer the query `name` with its arguments.
+    fn query(&self, name: &str, args: &[&str]) -> anyhow::Result<String>;
+}
+
+fn help<T: Repl>() -> String {
+    let mut out = format!(
+        "step [n]        advance n steps, one step is {}\n\
+         show            print the current state\n\
+         reset           reload the state from the input\n\
+         query <q> ...   ask a question, one of:\n",
+        T::STEP,
+    );
+    for (name, description) in T::QUERIES {
+        out.push_str(&format!("  {:<16}{}\n", name, description));
+    }
+    out.push_str("help            show this help\nquit            leave the REPL\n");
+
+    out
+}
+
+fn command<T: Repl>(state: &mut T, input: &str, line: &str) -> anyhow::Result<String> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+
+    match words.as_slice() {
+        [] => Ok(String::new()),
+        ["step"] => state.step(),
+        ["step", n] => {
+            let n: usize = n.parse()
+                .with_context(|| format!("Invalid number of steps: {}", n))?;
+            let mut out = String::new();
+            for _ in 0 .. n {
+                match state.step() {
+                    Ok(step) => {
+                        out.push_str(&step);
+                        out.push('\n');
+                    },
+                    // Keep what the steps before showed, stopping here
+                    Err(e) if !out.is_empty() => {
+                        out.push_str(&format!("Error: {:#}", e));
+                        break;
+                    },
+                    Err(e) => return Err(e),
+                }
+            }
+            Ok(out.trim_end().into())
+        },
+        ["show"] => Ok(state.show()),
+        ["reset"] => {
+            *state = T::load(input)?;
+            Ok(String::from("State reset"))
+        },
+        ["query", name, args @ ..] => state.query(name, args),
+        ["help"] => Ok(help::<T>()),
+        _ => bail!("Unknown command: {} (try help)", line.trim()),
+    }
+}
+
+/// Load the puzzle's state and run the REPL on it until `quit` or the end
+/// of standard input.
+pub fn run<T: Repl>(args: &Args, puzzle: Puzzle) -> anyhow::Result<()> {
+    let input = args.read(puzzle)?;
+    let mut state = T::load(&input)?;
+
+    let stdin = io::stdin();
+    let prompt = stdin.is_terminal();
+    let mut stdout = io::stdout();
+
+    loop {
+        if prompt {
+            write!(stdout, "> ")?;
+            stdout.flush()?;
+        }
+
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line)? == 0 {
+            break;
+        }
+        if matches!(line.trim(), "quit" | "exit") {
+            break;
+        }
+
+        match command(&mut state, &input, &line) {
+            Ok(out) if out.is_empty() => {},
+            Ok(out) => writeln!(stdout, "{}", out.trim_end())?,
+            Err(e) => eprintln!("Error: {:#}", e),
+        }
+    }
+
+    Ok(())
+}
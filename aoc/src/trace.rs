@@ -0,0 +1,44 @@
+//! Structured logging of solver internals.
+//!
+//! Days emit events with the `tracing` macros. Nothing is printed unless
+//! `-v`/`-vv` is passed or [`ENV_VAR`] is set, e.g.
+//! `AOC_LOG=day15_2021=trace cargo run -p day15-2021`.
+//!
+//! When logging is off no subscriber is installed, so the max level stays at
+//! `OFF` and each event short-circuits on a single static level comparison
+//! without formatting its fields.
+
+use std::io::IsTerminal;
+
+use tracing::level_filters::LevelFilter;
+use tracing_subscriber::EnvFilter;
+
+/// Environment variable holding `tracing` filter directives.
+/// Takes precedence over `-v` when set.
+pub const ENV_VAR: &str = "AOC_LOG";
+
+fn level(verbose: u8) -> LevelFilter {
+    match verbose {
+        0 => LevelFilter::OFF,
+        1 => LevelFilter::DEBUG,
+        _ => LevelFilter::TRACE,
+    }
+}
+
+/// Install a stderr subscriber for the given `-v` count, if any logging
+/// was requested.
+pub fn init(verbose: u8) {
+    let filter = match std::env::var(ENV_VAR) {
+        Ok(directives) => EnvFilter::new(directives),
+        Err(_) if verbose > 0 => EnvFilter::default()
+            .add_directive(level(verbose).into()),
+        Err(_) => return,
+    };
+
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .with_ansi(std::io::stderr().is_terminal())
+        .without_time()
+        .init();
+}